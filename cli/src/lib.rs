@@ -0,0 +1,240 @@
+//! Offline production of signed KYC proxy deploys.
+//!
+//! Every subcommand turns its arguments into a session with the `kyc-proxy-client` builders, signs
+//! the deploy with the given secret key and writes its JSON to the output file. Nothing is sent to a
+//! node, so this runs on air-gapped machines; the resulting file is submitted separately.
+
+use std::{convert::TryInto, fmt, fs, path::PathBuf};
+
+use casper_types::{ContractPackageHash, Key, SecretKey, U512};
+use clap::{Parser, Subcommand};
+use kyc_proxy_client::{
+    deploy_json, signed_deploy, AddKycProvider, Deploy, DeployParams, ProviderMetadataFields,
+    ProxyCall, ProxyInstall, TimeDiff, Timestamp, VerdictSession,
+};
+
+#[derive(Debug, Parser)]
+#[clap(
+    name = "kyc-proxy-cli",
+    about = "Produce signed deploys for the KYC proxy contract without network access"
+)]
+pub struct Cli {
+    /// Path to the PEM file of the secret key signing the deploy.
+    #[clap(long)]
+    pub secret_key: PathBuf,
+    /// Name of the chain the deploy is meant for.
+    #[clap(long)]
+    pub chain_name: String,
+    /// Payment amount in motes.
+    #[clap(long, default_value = "5000000000")]
+    pub payment_amount: String,
+    /// Deploy timestamp in RFC 3339 format, the current time if omitted.
+    #[clap(long)]
+    pub timestamp: Option<String>,
+    /// Time to live of the deploy, e.g. "30min".
+    #[clap(long, default_value = "30min")]
+    pub ttl: String,
+    /// File the deploy JSON is written to.
+    #[clap(long, short)]
+    pub output: PathBuf,
+    #[clap(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Install a new proxy contract.
+    Install {
+        /// Path to `kyc-proxy.wasm`.
+        #[clap(long)]
+        wasm: PathBuf,
+        /// Name of the proxy, prefixing the named keys of the installing account.
+        #[clap(long)]
+        name: String,
+        /// Package hash of a provider to register on installation, can be repeated.
+        #[clap(long = "initial-provider")]
+        initial_providers: Vec<String>,
+    },
+    /// Register a KYC provider with the proxy.
+    AddProvider {
+        #[clap(long)]
+        proxy_package_hash: String,
+        #[clap(long)]
+        provider: String,
+        #[clap(long)]
+        name: Option<String>,
+        #[clap(long)]
+        website: Option<String>,
+        #[clap(long)]
+        contact: Option<String>,
+        #[clap(long)]
+        description: Option<String>,
+        /// Mark the provider as another KYC proxy.
+        #[clap(long)]
+        is_proxy: bool,
+    },
+    /// Ban a registered KYC provider.
+    Ban {
+        #[clap(long)]
+        proxy_package_hash: String,
+        #[clap(long)]
+        provider: String,
+    },
+    /// Unban a registered KYC provider.
+    Unban {
+        #[clap(long)]
+        proxy_package_hash: String,
+        #[clap(long)]
+        provider: String,
+    },
+    /// Check an account through the `record_kyc_verdict` session code.
+    Check {
+        /// Path to `record_kyc_verdict.wasm`.
+        #[clap(long)]
+        wasm: PathBuf,
+        #[clap(long)]
+        proxy_package_hash: String,
+        /// Formatted key of the account to check, the signing account if omitted.
+        #[clap(long)]
+        account: Option<String>,
+        /// Named key to store the verdict under.
+        #[clap(long)]
+        named_key: Option<String>,
+    },
+}
+
+/// Error of a CLI invocation, holding a message for the operator.
+#[derive(Debug)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Build the signed deploy and write its JSON to the output file.
+pub fn run(cli: &Cli) -> Result<(), CliError> {
+    let json = build_deploy_json(cli)?;
+    fs::write(&cli.output, json)
+        .map_err(|error| CliError(format!("can not write {}: {}", cli.output.display(), error)))
+}
+
+/// Build the signed deploy of a CLI invocation, serialized as JSON.
+pub fn build_deploy_json(cli: &Cli) -> Result<String, CliError> {
+    let deploy = build_deploy(cli)?;
+    deploy_json(&deploy).map_err(|error| CliError(format!("can not serialize deploy: {}", error)))
+}
+
+/// Build the signed deploy of a CLI invocation.
+pub fn build_deploy(cli: &Cli) -> Result<Deploy, CliError> {
+    let secret_key = SecretKey::from_file(&cli.secret_key).map_err(|error| {
+        CliError(format!(
+            "can not load secret key {}: {}",
+            cli.secret_key.display(),
+            error
+        ))
+    })?;
+    let params = DeployParams {
+        chain_name: cli.chain_name.clone(),
+        timestamp: match &cli.timestamp {
+            Some(timestamp) => timestamp
+                .parse::<Timestamp>()
+                .map_err(|error| CliError(format!("invalid timestamp: {}", error)))?,
+            None => Timestamp::now(),
+        },
+        ttl: cli
+            .ttl
+            .parse::<TimeDiff>()
+            .map_err(|error| CliError(format!("invalid ttl: {}", error)))?,
+        payment_amount: U512::from_dec_str(&cli.payment_amount)
+            .map_err(|error| CliError(format!("invalid payment amount: {}", error)))?,
+    };
+
+    let session = match &cli.command {
+        Command::Install {
+            wasm,
+            name,
+            initial_providers,
+        } => {
+            let initial_providers = initial_providers
+                .iter()
+                .map(|provider| parse_package_hash(provider))
+                .collect::<Result<Vec<_>, _>>()?;
+            ProxyInstall::new(name)
+                .with_initial_providers(initial_providers)
+                .session(read_wasm(wasm)?)
+        }
+        Command::AddProvider {
+            proxy_package_hash,
+            provider,
+            name,
+            website,
+            contact,
+            description,
+            is_proxy,
+        } => {
+            let metadata = ProviderMetadataFields {
+                name: name.clone(),
+                website: website.clone(),
+                contact: contact.clone(),
+                description: description.clone(),
+            };
+            let call: ProxyCall = AddKycProvider::new(parse_package_hash(provider)?)
+                .with_metadata(metadata)
+                .as_proxy(*is_proxy)
+                .into();
+            call.session(parse_package_hash(proxy_package_hash)?)
+        }
+        Command::Ban {
+            proxy_package_hash,
+            provider,
+        } => ProxyCall::ban_provider(parse_package_hash(provider)?)
+            .session(parse_package_hash(proxy_package_hash)?),
+        Command::Unban {
+            proxy_package_hash,
+            provider,
+        } => ProxyCall::unban_provider(parse_package_hash(provider)?)
+            .session(parse_package_hash(proxy_package_hash)?),
+        Command::Check {
+            wasm,
+            proxy_package_hash,
+            account,
+            named_key,
+        } => {
+            let mut session = VerdictSession::new(parse_package_hash(proxy_package_hash)?);
+            if let Some(account) = account {
+                session = session.with_account(
+                    Key::from_formatted_str(account)
+                        .map_err(|error| CliError(format!("invalid account: {}", error)))?,
+                );
+            }
+            if let Some(named_key) = named_key {
+                session = session.with_named_key(named_key);
+            }
+            session.session(read_wasm(wasm)?)
+        }
+    };
+
+    Ok(signed_deploy(session, &params, &secret_key))
+}
+
+/// Parse a package hash given either formatted ("contract-package-wasm…") or as plain hex.
+fn parse_package_hash(input: &str) -> Result<ContractPackageHash, CliError> {
+    if let Ok(package_hash) = ContractPackageHash::from_formatted_str(input) {
+        return Ok(package_hash);
+    }
+    let bytes = hex::decode(input)
+        .map_err(|error| CliError(format!("invalid package hash {}: {}", input, error)))?;
+    let hash: [u8; 32] = bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| CliError(format!("package hash {} is not 32 bytes long", input)))?;
+    Ok(ContractPackageHash::new(hash))
+}
+
+fn read_wasm(path: &PathBuf) -> Result<Vec<u8>, CliError> {
+    fs::read(path).map_err(|error| CliError(format!("can not read {}: {}", path.display(), error)))
+}
@@ -0,0 +1,15 @@
+use std::process;
+
+use clap::Parser;
+use kyc_proxy_cli::Cli;
+
+fn main() {
+    let cli = Cli::parse();
+    match kyc_proxy_cli::run(&cli) {
+        Ok(()) => println!("wrote deploy to {}", cli.output.display()),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::{env, fs, path::PathBuf};
+
+use clap::Parser;
+use kyc_proxy_cli::{build_deploy_json, Cli};
+use serde_json::Value;
+
+const PROXY_PACKAGE_HASH: &str =
+    "contract-package-wasm0101010101010101010101010101010101010101010101010101010101010101";
+const PROVIDER: &str = "0202020202020202020202020202020202020202020202020202020202020202";
+const ACCOUNT: &str =
+    "account-hash-0303030303030303030303030303030303030303030303030303030303030303";
+
+/// Set to write the golden files instead of comparing against them; otherwise a missing one fails.
+const BLESS_VAR: &str = "KYC_PROXY_CLI_BLESS";
+
+fn manifest_path(relative: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(relative)
+        .display()
+        .to_string()
+}
+
+fn cli(subcommand: &[&str]) -> Cli {
+    let secret_key = manifest_path("tests/fixtures/secret_key.pem");
+    let mut argv = vec![
+        "kyc-proxy-cli",
+        "--secret-key",
+        &secret_key,
+        "--chain-name",
+        "casper-test",
+        "--timestamp",
+        "2022-06-01T12:00:00.000Z",
+        "--ttl",
+        "1h",
+        "--output",
+        "unused.json",
+    ];
+    argv.extend_from_slice(subcommand);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+fn assert_golden(name: &str, subcommand: &[&str]) -> Value {
+    let json = build_deploy_json(&cli(subcommand)).unwrap();
+    assert_eq!(json, build_deploy_json(&cli(subcommand)).unwrap());
+
+    let golden = PathBuf::from(manifest_path("tests/golden")).join(format!("{}.json", name));
+    if env::var_os(BLESS_VAR).is_some() {
+        fs::write(&golden, &json).unwrap();
+    } else {
+        let expected = fs::read_to_string(&golden).unwrap_or_else(|_| {
+            panic!(
+                "{} has no golden file {}, set {} to create it",
+                name,
+                golden.display(),
+                BLESS_VAR
+            )
+        });
+        assert_eq!(
+            json, expected,
+            "{} differs from the golden file, set {} to regenerate",
+            name, BLESS_VAR
+        );
+    }
+
+    let deploy: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(deploy["header"]["chain_name"], "casper-test");
+    assert_eq!(
+        deploy["header"]["account"],
+        deploy["approvals"][0]["signer"]
+    );
+    assert_eq!(deploy["approvals"].as_array().unwrap().len(), 1);
+    deploy
+}
+
+fn stored_entry_point(deploy: &Value) -> &str {
+    deploy["session"]["StoredVersionedContractByHash"]["entry_point"]
+        .as_str()
+        .unwrap()
+}
+
+#[test]
+fn test_install() {
+    let wasm = manifest_path("tests/fixtures/module.wasm");
+    let deploy = assert_golden(
+        "install",
+        &[
+            "install",
+            "--wasm",
+            &wasm,
+            "--name",
+            "kyc",
+            "--initial-provider",
+            PROVIDER,
+        ],
+    );
+    assert!(deploy["session"]["ModuleBytes"].is_object());
+}
+
+#[test]
+fn test_add_provider() {
+    let deploy = assert_golden(
+        "add_provider",
+        &[
+            "add-provider",
+            "--proxy-package-hash",
+            PROXY_PACKAGE_HASH,
+            "--provider",
+            PROVIDER,
+            "--name",
+            "Provider",
+            "--website",
+            "https://provider.example",
+        ],
+    );
+    assert_eq!(stored_entry_point(&deploy), "add_kyc_provider");
+}
+
+#[test]
+fn test_ban() {
+    let deploy = assert_golden(
+        "ban",
+        &[
+            "ban",
+            "--proxy-package-hash",
+            PROXY_PACKAGE_HASH,
+            "--provider",
+            PROVIDER,
+        ],
+    );
+    assert_eq!(stored_entry_point(&deploy), "ban_provider");
+}
+
+#[test]
+fn test_unban() {
+    let deploy = assert_golden(
+        "unban",
+        &[
+            "unban",
+            "--proxy-package-hash",
+            PROXY_PACKAGE_HASH,
+            "--provider",
+            PROVIDER,
+        ],
+    );
+    assert_eq!(stored_entry_point(&deploy), "unban_provider");
+}
+
+#[test]
+fn test_check() {
+    let wasm = manifest_path("tests/fixtures/module.wasm");
+    let deploy = assert_golden(
+        "check",
+        &[
+            "check",
+            "--wasm",
+            &wasm,
+            "--proxy-package-hash",
+            PROXY_PACKAGE_HASH,
+            "--account",
+            ACCOUNT,
+        ],
+    );
+    assert!(deploy["session"]["ModuleBytes"].is_object());
+}
+
+#[test]
+fn test_invalid_package_hash() {
+    let error = build_deploy_json(&cli(&[
+        "ban",
+        "--proxy-package-hash",
+        "not-a-hash",
+        "--provider",
+        PROVIDER,
+    ]))
+    .unwrap_err();
+    assert!(error.to_string().contains("not-a-hash"));
+}
@@ -0,0 +1,744 @@
+//! Typed builders for the runtime arguments of the proxy's installer, entry points and session code.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    account::AccountHash, runtime_args, ContractPackageHash, Key, PublicKey, RuntimeArgs, U256,
+};
+
+use kyc_proxy_core::{
+    AccountNormalization, ConsumerProfile, ProviderAdapter, ProviderAddressing, ProviderStatus,
+};
+
+use crate::names::{args, entry_points, mock_provider, provider};
+
+/// Arguments of the proxy's installer session code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProxyInstall {
+    name: String,
+    initial_providers: Option<Vec<ContractPackageHash>>,
+}
+
+impl ProxyInstall {
+    /// Install a proxy whose named keys are prefixed with `name`.
+    pub fn new(name: &str) -> Self {
+        ProxyInstall {
+            name: name.into(),
+            initial_providers: None,
+        }
+    }
+
+    /// Register the given providers on installation.
+    pub fn with_initial_providers(mut self, initial_providers: Vec<ContractPackageHash>) -> Self {
+        self.initial_providers = Some(initial_providers);
+        self
+    }
+
+    pub fn args(&self) -> RuntimeArgs {
+        runtime_args! {
+            args::PROXY_NAME => self.name.clone(),
+            args::INITIAL_PROVIDERS => self.initial_providers.clone()
+        }
+    }
+}
+
+/// Metadata fields of a provider, passed to `add_kyc_provider` or `update_provider_metadata`.
+/// Fields left as `None` are not passed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProviderMetadataFields {
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub contact: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ProviderMetadataFields {
+    fn insert_into(&self, runtime_args: &mut RuntimeArgs) {
+        let fields = [
+            (args::PROVIDER_NAME, &self.name),
+            (args::WEBSITE, &self.website),
+            (args::CONTACT, &self.contact),
+            (args::DESCRIPTION, &self.description),
+        ];
+        for (arg_name, value) in fields.iter() {
+            if let Some(value) = value {
+                runtime_args
+                    .insert(*arg_name, value.clone())
+                    .expect("should serialize string.");
+            }
+        }
+    }
+}
+
+/// Arguments of `add_kyc_provider`, whose metadata, proxy flag, adapter, addressing and account
+/// normalization are optional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddKycProvider {
+    provider: ContractPackageHash,
+    metadata: ProviderMetadataFields,
+    is_proxy: Option<bool>,
+    adapter: Option<ProviderAdapter>,
+    addressing: Option<ProviderAddressing>,
+    normalization: Option<AccountNormalization>,
+}
+
+impl AddKycProvider {
+    pub fn new(provider: ContractPackageHash) -> Self {
+        AddKycProvider {
+            provider,
+            metadata: ProviderMetadataFields::default(),
+            is_proxy: None,
+            adapter: None,
+            addressing: None,
+            normalization: None,
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: ProviderMetadataFields) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Mark the provider as another KYC proxy.
+    pub fn as_proxy(mut self, is_proxy: bool) -> Self {
+        self.is_proxy = Some(is_proxy);
+        self
+    }
+
+    /// Ask the provider through `adapter` instead of its `is_kyc_proved` entry point.
+    pub fn with_adapter(mut self, adapter: ProviderAdapter) -> Self {
+        self.adapter = Some(adapter);
+        self
+    }
+
+    /// Call the provider at a contract hash, given as `provider`, instead of a contract package.
+    pub fn with_addressing(mut self, addressing: ProviderAddressing) -> Self {
+        self.addressing = Some(addressing);
+        self
+    }
+
+    /// Restrict the account keys the provider is asked with.
+    pub fn with_normalization(mut self, normalization: AccountNormalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+}
+
+impl From<AddKycProvider> for ProxyCall {
+    fn from(add_kyc_provider: AddKycProvider) -> Self {
+        let mut call_args = provider_args(add_kyc_provider.provider);
+        add_kyc_provider.metadata.insert_into(&mut call_args);
+        if let Some(is_proxy) = add_kyc_provider.is_proxy {
+            call_args
+                .insert(args::IS_PROXY, is_proxy)
+                .expect("should serialize bool.");
+        }
+        if let Some(adapter) = add_kyc_provider.adapter {
+            call_args
+                .insert(args::ADAPTER, adapter)
+                .expect("should serialize adapter.");
+        }
+        if let Some(addressing) = add_kyc_provider.addressing {
+            call_args
+                .insert(args::ADDRESSING, addressing)
+                .expect("should serialize addressing.");
+        }
+        if let Some(normalization) = add_kyc_provider.normalization {
+            call_args
+                .insert(args::NORMALIZATION, normalization)
+                .expect("should serialize normalization.");
+        }
+        ProxyCall::new(entry_points::ADD_KYC_PROVIDER, call_args)
+    }
+}
+
+/// A call of one entry point of an installed proxy, together with its runtime arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProxyCall {
+    entry_point: &'static str,
+    args: RuntimeArgs,
+}
+
+impl ProxyCall {
+    fn new(entry_point: &'static str, args: RuntimeArgs) -> Self {
+        ProxyCall { entry_point, args }
+    }
+
+    pub fn entry_point(&self) -> &'static str {
+        self.entry_point
+    }
+
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    pub fn into_args(self) -> RuntimeArgs {
+        self.args
+    }
+
+    pub fn init(initial_providers: Vec<ContractPackageHash>) -> Self {
+        Self::new(
+            entry_points::INIT,
+            runtime_args! { args::INITIAL_PROVIDERS => initial_providers },
+        )
+    }
+
+    pub fn is_kyc_proved(account: Key, index: Option<U256>) -> Self {
+        Self::new(
+            entry_points::IS_KYC_PROVED,
+            runtime_args! {
+                args::ACCOUNT => account,
+                args::INDEX => index
+            },
+        )
+    }
+
+    pub fn is_kyc_proved_for_public_key(public_key: PublicKey, index: Option<U256>) -> Self {
+        Self::new(
+            entry_points::IS_KYC_PROVED_FOR_PUBLIC_KEY,
+            runtime_args! {
+                args::PUBLIC_KEY => public_key,
+                args::INDEX => index
+            },
+        )
+    }
+
+    /// Check the account sending the deploy.
+    pub fn am_i_kyc_proved(index: Option<U256>) -> Self {
+        Self::new(
+            entry_points::AM_I_KYC_PROVED,
+            runtime_args! { args::INDEX => index },
+        )
+    }
+
+    pub fn is_kyc_proved_in(account: Key, index: Option<U256>, jurisdictions: Vec<String>) -> Self {
+        Self::new(
+            entry_points::IS_KYC_PROVED_IN,
+            runtime_args! {
+                args::ACCOUNT => account,
+                args::INDEX => index,
+                args::JURISDICTIONS => jurisdictions
+            },
+        )
+    }
+
+    /// Check `account` only with the given providers.
+    pub fn is_kyc_proved_with(
+        account: Key,
+        index: Option<U256>,
+        providers: Vec<ContractPackageHash>,
+    ) -> Self {
+        Self::new(
+            entry_points::IS_KYC_PROVED_WITH,
+            runtime_args! {
+                args::ACCOUNT => account,
+                args::INDEX => index,
+                args::PROVIDERS => providers
+            },
+        )
+    }
+
+    pub fn are_kyc_proved(accounts: Vec<Key>, index: Option<U256>) -> Self {
+        Self::new(entry_points::ARE_KYC_PROVED, accounts_args(accounts, index))
+    }
+
+    pub fn all_kyc_proved(accounts: Vec<Key>, index: Option<U256>) -> Self {
+        Self::new(entry_points::ALL_KYC_PROVED, accounts_args(accounts, index))
+    }
+
+    pub fn any_kyc_proved(accounts: Vec<Key>, index: Option<U256>) -> Self {
+        Self::new(entry_points::ANY_KYC_PROVED, accounts_args(accounts, index))
+    }
+
+    /// Register a provider without metadata. Use `AddKycProvider` to pass the optional arguments.
+    pub fn add_kyc_provider(provider: ContractPackageHash) -> Self {
+        AddKycProvider::new(provider).into()
+    }
+
+    pub fn ban_provider(provider: ContractPackageHash) -> Self {
+        Self::new(entry_points::BAN_PROVIDER, provider_args(provider))
+    }
+
+    /// Ban a provider, storing `reason` in its status.
+    pub fn ban_provider_for(provider: ContractPackageHash, reason: String) -> Self {
+        let mut call_args = provider_args(provider);
+        call_args
+            .insert(args::REASON, reason)
+            .expect("should serialize reason.");
+        Self::new(entry_points::BAN_PROVIDER, call_args)
+    }
+
+    pub fn unban_provider(provider: ContractPackageHash) -> Self {
+        Self::new(entry_points::UNBAN_PROVIDER, provider_args(provider))
+    }
+
+    pub fn set_provider_status(provider: ContractPackageHash, status: ProviderStatus) -> Self {
+        let mut call_args = provider_args(provider);
+        call_args
+            .insert(args::STATUS, status)
+            .expect("should serialize status.");
+        Self::new(entry_points::SET_PROVIDER_STATUS, call_args)
+    }
+
+    pub fn get_provider_status(provider: ContractPackageHash) -> Self {
+        Self::new(entry_points::GET_PROVIDER_STATUS, provider_args(provider))
+    }
+
+    pub fn set_provider_jurisdictions(
+        provider: ContractPackageHash,
+        jurisdictions: Vec<String>,
+    ) -> Self {
+        let mut call_args = provider_args(provider);
+        call_args
+            .insert(args::JURISDICTIONS, jurisdictions)
+            .expect("should serialize jurisdictions.");
+        Self::new(entry_points::SET_PROVIDER_JURISDICTIONS, call_args)
+    }
+
+    pub fn set_provider_is_proxy(provider: ContractPackageHash, is_proxy: bool) -> Self {
+        let mut call_args = provider_args(provider);
+        call_args
+            .insert(args::IS_PROXY, is_proxy)
+            .expect("should serialize bool.");
+        Self::new(entry_points::SET_PROVIDER_IS_PROXY, call_args)
+    }
+
+    pub fn set_max_proxy_depth(max_depth: u8) -> Self {
+        Self::new(
+            entry_points::SET_MAX_PROXY_DEPTH,
+            runtime_args! { args::MAX_DEPTH => max_depth },
+        )
+    }
+
+    pub fn update_provider_metadata(
+        provider: ContractPackageHash,
+        metadata: ProviderMetadataFields,
+    ) -> Self {
+        let mut call_args = provider_args(provider);
+        metadata.insert_into(&mut call_args);
+        Self::new(entry_points::UPDATE_PROVIDER_METADATA, call_args)
+    }
+
+    pub fn get_provider_info(provider: ContractPackageHash) -> Self {
+        Self::new(entry_points::GET_PROVIDER_INFO, provider_args(provider))
+    }
+
+    pub fn get_provider_adapter(provider: ContractPackageHash) -> Self {
+        Self::new(entry_points::GET_PROVIDER_ADAPTER, provider_args(provider))
+    }
+
+    pub fn get_provider_addressing(provider: ContractPackageHash) -> Self {
+        Self::new(
+            entry_points::GET_PROVIDER_ADDRESSING,
+            provider_args(provider),
+        )
+    }
+
+    pub fn get_provider_normalization(provider: ContractPackageHash) -> Self {
+        Self::new(
+            entry_points::GET_PROVIDER_NORMALIZATION,
+            provider_args(provider),
+        )
+    }
+
+    /// Propose, as the primary account sending the deploy, to link `secondary`.
+    pub fn propose_link(secondary: AccountHash) -> Self {
+        Self::new(
+            entry_points::PROPOSE_LINK,
+            runtime_args! { args::SECONDARY => Key::Account(secondary) },
+        )
+    }
+
+    /// Accept, as the secondary account sending the deploy, the link proposed by `primary`.
+    pub fn accept_link(primary: AccountHash) -> Self {
+        Self::new(
+            entry_points::ACCEPT_LINK,
+            runtime_args! { args::PRIMARY => Key::Account(primary) },
+        )
+    }
+
+    /// Remove the link between the account sending the deploy and `account`.
+    pub fn unlink(account: AccountHash) -> Self {
+        Self::new(entry_points::UNLINK, account_args(account))
+    }
+
+    pub fn set_max_links_per_primary(max_links: u32) -> Self {
+        Self::new(
+            entry_points::SET_MAX_LINKS_PER_PRIMARY,
+            runtime_args! { args::MAX_LINKS => max_links },
+        )
+    }
+
+    pub fn get_primary_account(account: AccountHash) -> Self {
+        Self::new(entry_points::GET_PRIMARY_ACCOUNT, account_args(account))
+    }
+
+    pub fn get_linked_accounts(account: AccountHash) -> Self {
+        Self::new(entry_points::GET_LINKED_ACCOUNTS, account_args(account))
+    }
+
+    pub fn set_privacy_mode(enabled: bool) -> Self {
+        Self::new(
+            entry_points::SET_PRIVACY_MODE,
+            runtime_args! { args::ENABLED => enabled },
+        )
+    }
+
+    pub fn set_third_party_access(consumer: ContractPackageHash, allowed: bool) -> Self {
+        Self::new(
+            entry_points::SET_THIRD_PARTY_ACCESS,
+            runtime_args! {
+                args::CONSUMER => Key::Hash(consumer.value()),
+                args::ALLOWED => allowed
+            },
+        )
+    }
+
+    pub fn has_third_party_access(consumer: ContractPackageHash) -> Self {
+        Self::new(
+            entry_points::HAS_THIRD_PARTY_ACCESS,
+            runtime_args! { args::CONSUMER => Key::Hash(consumer.value()) },
+        )
+    }
+
+    /// Register `consumer`, a `Key::Hash` of a contract package or a `Key::Account`.
+    pub fn add_consumer(consumer: Key) -> Self {
+        Self::new(
+            entry_points::ADD_CONSUMER,
+            runtime_args! { args::CONSUMER => consumer },
+        )
+    }
+
+    pub fn remove_consumer(consumer: Key) -> Self {
+        Self::new(
+            entry_points::REMOVE_CONSUMER,
+            runtime_args! { args::CONSUMER => consumer },
+        )
+    }
+
+    pub fn is_consumer(consumer: Key) -> Self {
+        Self::new(
+            entry_points::IS_CONSUMER,
+            runtime_args! { args::CONSUMER => consumer },
+        )
+    }
+
+    pub fn set_restricted_mode(enabled: bool) -> Self {
+        Self::new(
+            entry_points::SET_RESTRICTED_MODE,
+            runtime_args! { args::ENABLED => enabled },
+        )
+    }
+
+    pub fn set_provider_tier(provider: ContractPackageHash, tier: u8) -> Self {
+        let mut call_args = provider_args(provider);
+        call_args
+            .insert(args::TIER, tier)
+            .expect("should serialize tier.");
+        Self::new(entry_points::SET_PROVIDER_TIER, call_args)
+    }
+
+    pub fn get_provider_tier(provider: ContractPackageHash) -> Self {
+        Self::new(entry_points::GET_PROVIDER_TIER, provider_args(provider))
+    }
+
+    pub fn set_consumer_profile(consumer: ContractPackageHash, profile: ConsumerProfile) -> Self {
+        let mut call_args = runtime_args! {
+            args::CONSUMER => Key::Hash(consumer.value()),
+            args::THRESHOLD => profile.threshold,
+            args::MIN_TIER => profile.min_tier
+        };
+        if let Some(providers) = profile.providers {
+            call_args
+                .insert(args::PROVIDERS, providers)
+                .expect("should serialize providers.");
+        }
+        Self::new(entry_points::SET_CONSUMER_PROFILE, call_args)
+    }
+
+    pub fn remove_consumer_profile(consumer: ContractPackageHash) -> Self {
+        Self::new(
+            entry_points::REMOVE_CONSUMER_PROFILE,
+            runtime_args! { args::CONSUMER => Key::Hash(consumer.value()) },
+        )
+    }
+
+    pub fn get_consumer_profile(consumer: ContractPackageHash) -> Self {
+        Self::new(
+            entry_points::GET_CONSUMER_PROFILE,
+            runtime_args! { args::CONSUMER => Key::Hash(consumer.value()) },
+        )
+    }
+
+    pub fn set_usage_metering(enabled: bool) -> Self {
+        Self::new(
+            entry_points::SET_USAGE_METERING,
+            runtime_args! { args::ENABLED => enabled },
+        )
+    }
+
+    /// Return the checks called by `consumer`, a `Key::Hash` of a contract package or a `Key::Account`.
+    pub fn get_consumer_usage_stats(consumer: Key) -> Self {
+        Self::new(
+            entry_points::GET_CONSUMER_USAGE_STATS,
+            runtime_args! { args::CONSUMER => consumer },
+        )
+    }
+
+    pub fn get_provider_usage_stats(provider: ContractPackageHash) -> Self {
+        Self::new(
+            entry_points::GET_PROVIDER_USAGE_STATS,
+            provider_args(provider),
+        )
+    }
+
+    pub fn reset_usage() -> Self {
+        Self::new(entry_points::RESET_USAGE, RuntimeArgs::new())
+    }
+
+    /// Grant `admin` admin rights over the proxy, as an admin sending the deploy.
+    pub fn add_admin(admin: AccountHash) -> Self {
+        Self::new(
+            entry_points::ADD_ADMIN,
+            runtime_args! { args::ADMIN => Key::Account(admin) },
+        )
+    }
+
+    pub fn remove_admin(admin: AccountHash) -> Self {
+        Self::new(
+            entry_points::REMOVE_ADMIN,
+            runtime_args! { args::ADMIN => Key::Account(admin) },
+        )
+    }
+
+    pub fn is_admin(admin: AccountHash) -> Self {
+        Self::new(
+            entry_points::IS_ADMIN,
+            runtime_args! { args::ADMIN => Key::Account(admin) },
+        )
+    }
+
+    pub fn migrate_storage() -> Self {
+        Self::new(entry_points::MIGRATE_STORAGE, RuntimeArgs::new())
+    }
+}
+
+/// Arguments of the `record_kyc_verdict` session code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerdictSession {
+    kyc_proxy_package_hash: ContractPackageHash,
+    account: Option<Key>,
+    index: Option<U256>,
+    named_key: Option<String>,
+}
+
+impl VerdictSession {
+    /// Check the deploy's account with the given proxy.
+    pub fn new(kyc_proxy_package_hash: ContractPackageHash) -> Self {
+        VerdictSession {
+            kyc_proxy_package_hash,
+            account: None,
+            index: None,
+            named_key: None,
+        }
+    }
+
+    /// Check the given account instead of the deploy's account.
+    pub fn with_account(mut self, account: Key) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    pub fn with_index(mut self, index: U256) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Store the verdict under the given named key instead of the default one.
+    pub fn with_named_key(mut self, named_key: &str) -> Self {
+        self.named_key = Some(named_key.into());
+        self
+    }
+
+    pub fn args(&self) -> RuntimeArgs {
+        runtime_args! {
+            args::KYC_PROXY_PACKAGE_HASH => self.kyc_proxy_package_hash,
+            args::ACCOUNT => self.account,
+            args::INDEX => self.index,
+            args::NAMED_KEY => self.named_key.clone()
+        }
+    }
+}
+
+/// Arguments of the reference provider's installer session code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderInstall {
+    name: String,
+    admins: Option<Vec<Key>>,
+}
+
+impl ProviderInstall {
+    /// Install a provider whose named keys are prefixed with `name`, administered by the deploy's
+    /// account.
+    pub fn new(name: &str) -> Self {
+        ProviderInstall {
+            name: name.into(),
+            admins: None,
+        }
+    }
+
+    /// Grant approval rights to the given accounts instead of the deploy's account.
+    pub fn with_admins(mut self, admins: Vec<Key>) -> Self {
+        self.admins = Some(admins);
+        self
+    }
+
+    pub fn args(&self) -> RuntimeArgs {
+        runtime_args! {
+            provider::args::PROVIDER_NAME => self.name.clone(),
+            provider::args::ADMINS => self.admins.clone()
+        }
+    }
+}
+
+/// A call of one entry point of an installed reference provider, together with its runtime
+/// arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderCall {
+    entry_point: &'static str,
+    args: RuntimeArgs,
+}
+
+impl ProviderCall {
+    fn new(entry_point: &'static str, args: RuntimeArgs) -> Self {
+        ProviderCall { entry_point, args }
+    }
+
+    pub fn entry_point(&self) -> &'static str {
+        self.entry_point
+    }
+
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    pub fn into_args(self) -> RuntimeArgs {
+        self.args
+    }
+
+    /// Approve `account` for `index`, until block time `expires_at` if given.
+    pub fn approve(account: Key, index: Option<U256>, expires_at: Option<u64>) -> Self {
+        Self::new(
+            provider::entry_points::APPROVE,
+            runtime_args! {
+                provider::args::ACCOUNT => account,
+                provider::args::INDEX => index,
+                provider::args::EXPIRES_AT => expires_at
+            },
+        )
+    }
+
+    pub fn revoke(account: Key, index: Option<U256>) -> Self {
+        Self::new(
+            provider::entry_points::REVOKE,
+            runtime_args! {
+                provider::args::ACCOUNT => account,
+                provider::args::INDEX => index
+            },
+        )
+    }
+
+    pub fn add_admin(admin: Key) -> Self {
+        Self::new(
+            provider::entry_points::ADD_ADMIN,
+            runtime_args! { provider::args::ADMIN => admin },
+        )
+    }
+
+    pub fn remove_admin(admin: Key) -> Self {
+        Self::new(
+            provider::entry_points::REMOVE_ADMIN,
+            runtime_args! { provider::args::ADMIN => admin },
+        )
+    }
+}
+
+/// How the mock provider answers `is_kyc_proved` for an account.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MockAnswer {
+    False = 0,
+    True = 1,
+    /// Revert the call with `ApiError::User(500)`.
+    Revert = 2,
+    /// Loop until the deploy runs out of gas.
+    BurnGas = 3,
+}
+
+impl MockAnswer {
+    pub fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(MockAnswer::False),
+            1 => Some(MockAnswer::True),
+            2 => Some(MockAnswer::Revert),
+            3 => Some(MockAnswer::BurnGas),
+            _ => None,
+        }
+    }
+}
+
+/// A call of one entry point of an installed mock provider, together with its runtime arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockProviderCall {
+    entry_point: &'static str,
+    args: RuntimeArgs,
+}
+
+impl MockProviderCall {
+    pub fn entry_point(&self) -> &'static str {
+        self.entry_point
+    }
+
+    pub fn args(&self) -> &RuntimeArgs {
+        &self.args
+    }
+
+    pub fn into_args(self) -> RuntimeArgs {
+        self.args
+    }
+
+    /// Answer `answer` for `account`, whatever the index.
+    pub fn set_answer(account: Key, answer: MockAnswer) -> Self {
+        MockProviderCall {
+            entry_point: mock_provider::entry_points::SET_ANSWER,
+            args: runtime_args! {
+                mock_provider::args::ACCOUNT => account,
+                mock_provider::args::ANSWER => answer as u8
+            },
+        }
+    }
+
+    /// Answer `answer` for accounts without a scripted answer of their own.
+    pub fn set_default_answer(answer: MockAnswer) -> Self {
+        MockProviderCall {
+            entry_point: mock_provider::entry_points::SET_DEFAULT_ANSWER,
+            args: runtime_args! { mock_provider::args::ANSWER => answer as u8 },
+        }
+    }
+}
+
+/// The proxy expects providers as `Key::Hash` of their package hash.
+fn provider_args(provider: ContractPackageHash) -> RuntimeArgs {
+    runtime_args! { args::PROVIDER => Key::Hash(provider.value()) }
+}
+
+fn account_args(account: AccountHash) -> RuntimeArgs {
+    runtime_args! { args::ACCOUNT => Key::Account(account) }
+}
+
+fn accounts_args(accounts: Vec<Key>, index: Option<U256>) -> RuntimeArgs {
+    runtime_args! {
+        args::ACCOUNTS => accounts,
+        args::INDEX => index
+    }
+}
@@ -0,0 +1,132 @@
+//! Turning the builders' runtime arguments into deploy items for the engine, and into signed deploys for a node.
+
+use std::collections::BTreeSet;
+
+use casper_execution_engine::core::engine_state::{
+    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem,
+};
+pub use casper_node::types::{Deploy, TimeDiff, Timestamp};
+use casper_types::{
+    account::AccountHash, bytesrepr::Bytes, runtime_args, system::standard_payment::ARG_AMOUNT,
+    ContractPackageHash, DeployHash, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+
+use crate::call::{ProviderCall, ProviderInstall, ProxyCall, ProxyInstall, VerdictSession};
+
+/// Gas price of the deploys built by this crate.
+pub const DEFAULT_GAS_PRICE: u64 = 1;
+
+impl ProxyCall {
+    /// Session calling this entry point on the latest version of the proxy package.
+    pub fn session(&self, proxy_package_hash: ContractPackageHash) -> ExecutableDeployItem {
+        ExecutableDeployItem::StoredVersionedContractByHash {
+            hash: proxy_package_hash,
+            version: None,
+            entry_point: self.entry_point().into(),
+            args: self.args().clone(),
+        }
+    }
+}
+
+impl ProviderCall {
+    /// Session calling this entry point on the latest version of the provider package.
+    pub fn session(&self, provider_package_hash: ContractPackageHash) -> ExecutableDeployItem {
+        ExecutableDeployItem::StoredVersionedContractByHash {
+            hash: provider_package_hash,
+            version: None,
+            entry_point: self.entry_point().into(),
+            args: self.args().clone(),
+        }
+    }
+}
+
+impl ProviderInstall {
+    /// Session running the provider's installer, given the bytes of `kyc-provider.wasm`.
+    pub fn session(&self, module_bytes: Vec<u8>) -> ExecutableDeployItem {
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(module_bytes),
+            args: self.args(),
+        }
+    }
+}
+
+impl ProxyInstall {
+    /// Session running the proxy's installer, given the bytes of `kyc-proxy.wasm`.
+    pub fn session(&self, module_bytes: Vec<u8>) -> ExecutableDeployItem {
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(module_bytes),
+            args: self.args(),
+        }
+    }
+}
+
+impl VerdictSession {
+    /// Session running `record_kyc_verdict`, given the bytes of `record_kyc_verdict.wasm`.
+    pub fn session(&self, module_bytes: Vec<u8>) -> ExecutableDeployItem {
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(module_bytes),
+            args: self.args(),
+        }
+    }
+}
+
+/// Payment through the standard payment contract.
+pub fn standard_payment(amount: U512) -> ExecutableDeployItem {
+    ExecutableDeployItem::ModuleBytes {
+        module_bytes: Bytes::new(),
+        args: runtime_args! { ARG_AMOUNT => amount },
+    }
+}
+
+/// Build a deploy item for the execution engine, authorized by the account itself.
+pub fn deploy_item(
+    session: ExecutableDeployItem,
+    account: AccountHash,
+    payment_amount: U512,
+    deploy_hash: [u8; 32],
+) -> DeployItem {
+    let mut authorization_keys = BTreeSet::new();
+    authorization_keys.insert(account);
+    DeployItem::new(
+        account,
+        session,
+        standard_payment(payment_amount),
+        DEFAULT_GAS_PRICE,
+        authorization_keys,
+        DeployHash::new(deploy_hash),
+    )
+}
+
+/// Chain, timing and payment parameters of a signed deploy.
+#[derive(Clone, Debug)]
+pub struct DeployParams {
+    pub chain_name: String,
+    pub timestamp: Timestamp,
+    pub ttl: TimeDiff,
+    pub payment_amount: U512,
+}
+
+/// Build a deploy signed with `secret_key`, whose account is the key's public key.
+/// Signing is deterministic, so the same inputs always yield the same deploy.
+pub fn signed_deploy(
+    session: ExecutableDeployItem,
+    params: &DeployParams,
+    secret_key: &SecretKey,
+) -> Deploy {
+    Deploy::new(
+        params.timestamp,
+        params.ttl,
+        DEFAULT_GAS_PRICE,
+        Vec::new(),
+        params.chain_name.clone(),
+        standard_payment(params.payment_amount),
+        session,
+        secret_key,
+        Some(PublicKey::from(secret_key)),
+    )
+}
+
+/// Serialize a deploy into the JSON accepted by a node's `account_put_deploy` RPC.
+pub fn deploy_json(deploy: &Deploy) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(deploy)
+}
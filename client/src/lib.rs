@@ -0,0 +1,32 @@
+//! Typed builders for deploys of the KYC proxy contract.
+//!
+//! The `names` module, re-exported from `kyc-proxy-core`, defines the entry point, argument and
+//! named key names once; the contracts use it as well. The `std` feature adds conversion of the
+//! builders into engine deploy items and signed deploy JSON, and the `registry` module decoding the
+//! proxy's provider dictionary from raw global state.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod call;
+#[cfg(feature = "std")]
+pub mod deploy;
+#[cfg(feature = "std")]
+pub mod registry;
+
+pub use kyc_proxy_core::{
+    names, AccountNormalization, CallSpec, ConsumerProfile, ProviderAdapter, ProviderAddressing,
+    ProviderStatus, ProviderUsage, ReturnKind,
+};
+
+pub use call::{
+    AddKycProvider, MockAnswer, MockProviderCall, ProviderCall, ProviderInstall,
+    ProviderMetadataFields, ProxyCall, ProxyInstall, VerdictSession,
+};
+#[cfg(feature = "std")]
+pub use deploy::{
+    deploy_item, deploy_json, signed_deploy, standard_payment, Deploy, DeployParams, TimeDiff,
+    Timestamp,
+};
+#[cfg(feature = "std")]
+pub use registry::{RegistryEntry, RegistrySnapshot, RegistryUrefs};
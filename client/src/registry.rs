@@ -0,0 +1,186 @@
+//! Offline decoding of the proxy's provider registry from raw global state.
+//!
+//! The registry is kept under three named keys of the proxy contract: the "kyc_provider_index"
+//! dictionary holding the `ContractPackageHash` of each provider under its decimal index, the
+//! "kyc_provider_records" dictionary holding its `ProviderRecord` under the package hash string,
+//! and the "kyc_provider_count" URef holding the number of registered providers as `u64`.
+//! The functions below compute the global state address of each entry from those URefs, and
+//! `RegistrySnapshot` decodes the stored values found there.
+
+use std::{collections::BTreeMap, fmt};
+
+use casper_execution_engine::shared::stored_value::StoredValue;
+use casper_types::{
+    bytesrepr::{self, Bytes, FromBytes},
+    CLType, CLTyped, CLValue, CLValueError, ContractPackageHash, Key, URef,
+};
+use kyc_proxy_core::{ProviderRecord, ProviderStatus};
+
+/// URefs of the proxy contract's named keys holding the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistryUrefs {
+    /// Seed of the "kyc_provider_index" dictionary.
+    pub index: URef,
+    /// Seed of the "kyc_provider_records" dictionary.
+    pub records: URef,
+    /// The "kyc_provider_count" URef.
+    pub count: URef,
+}
+
+/// Global state address of the dictionary entry stored under `item_key`.
+pub fn dictionary_address(seed_uref: URef, item_key: &str) -> Key {
+    Key::dictionary(seed_uref, item_key.as_bytes())
+}
+
+/// Address of the number of registered providers.
+pub fn count_address(urefs: &RegistryUrefs) -> Key {
+    Key::URef(urefs.count.remove_access_rights())
+}
+
+/// Address of the package hash of the provider registered at `index`.
+pub fn index_address(urefs: &RegistryUrefs, index: u64) -> Key {
+    dictionary_address(urefs.index, &index.to_string())
+}
+
+/// Address of the record of `provider`.
+pub fn record_address(urefs: &RegistryUrefs, provider: ContractPackageHash) -> Key {
+    dictionary_address(urefs.records, &provider.to_string())
+}
+
+/// A provider of the registry with its position in registration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryEntry {
+    pub index: u64,
+    pub package_hash: ContractPackageHash,
+    pub status: ProviderStatus,
+}
+
+/// Decoded content of the registry, providers ordered by index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrySnapshot {
+    pub providers: Vec<RegistryEntry>,
+}
+
+/// Error decoding the registry.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// No stored value was given for the address.
+    Missing(Key),
+    /// The stored value at the address is not a `CLValue`.
+    NotCLValue(Key),
+    /// The `CLValue` at the address does not hold the expected type.
+    InvalidValue(Key, CLValueError),
+    /// The dictionary wrapper around the value at the address is malformed.
+    InvalidWrapper(Key, bytesrepr::Error),
+    /// The record at the address points to another index than the provider is stored at.
+    IndexMismatch(Key),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Missing(key) => write!(formatter, "missing stored value at {}", key),
+            DecodeError::NotCLValue(key) => write!(formatter, "no CLValue stored at {}", key),
+            DecodeError::InvalidValue(key, error) => {
+                write!(formatter, "invalid value at {}: {:?}", key, error)
+            }
+            DecodeError::InvalidWrapper(key, error) => {
+                write!(formatter, "invalid dictionary value at {}: {}", key, error)
+            }
+            DecodeError::IndexMismatch(key) => {
+                write!(formatter, "record at {} has another index", key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl RegistrySnapshot {
+    /// Decode the registry from stored values keyed by their global state address, as pulled
+    /// from the addresses computed by `count_address`, `index_address` and `record_address`.
+    pub fn from_stored_values(
+        urefs: &RegistryUrefs,
+        stored_values: &BTreeMap<Key, StoredValue>,
+    ) -> Result<Self, DecodeError> {
+        Self::from_lookup(urefs, |key| stored_values.get(key).cloned())
+    }
+
+    /// Decode the registry, reading each address from `lookup` as its predecessors are decoded.
+    /// Index entries are needed before the record address of their provider is known.
+    pub fn from_lookup<F>(urefs: &RegistryUrefs, mut lookup: F) -> Result<Self, DecodeError>
+    where
+        F: FnMut(&Key) -> Option<StoredValue>,
+    {
+        let count: u64 = decode_entry(count_address(urefs), &mut lookup)?;
+        let mut providers = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let package_hash: ContractPackageHash =
+                decode_entry(index_address(urefs, index), &mut lookup)?;
+            let address = record_address(urefs, package_hash);
+            let record: ProviderRecord = decode_entry(address, &mut lookup)?;
+            if record.index != index {
+                return Err(DecodeError::IndexMismatch(address));
+            }
+            providers.push(RegistryEntry {
+                index,
+                package_hash,
+                status: record.status,
+            });
+        }
+        Ok(RegistrySnapshot { providers })
+    }
+
+    /// Package hashes of the unbanned providers, in registration order.
+    /// Suspended providers are included, as the snapshot does not know the block time.
+    pub fn active_providers(&self) -> Vec<ContractPackageHash> {
+        self.providers
+            .iter()
+            .filter(|entry| !entry.status.is_banned())
+            .map(|entry| entry.package_hash)
+            .collect()
+    }
+}
+
+/// Read and decode the value at `address`. In global state, dictionary entries are stored wrapped
+/// together with their seed and item key as a `CLType::Any` value; values queried through the
+/// engine's dictionary lookup are already unwrapped, so both forms are accepted.
+fn decode_entry<T, F>(address: Key, lookup: &mut F) -> Result<T, DecodeError>
+where
+    T: CLTyped + FromBytes,
+    F: FnMut(&Key) -> Option<StoredValue>,
+{
+    let cl_value = match lookup(&address).ok_or(DecodeError::Missing(address))? {
+        StoredValue::CLValue(cl_value) => cl_value,
+        _ => return Err(DecodeError::NotCLValue(address)),
+    };
+    let cl_value = match (address, cl_value.cl_type()) {
+        (Key::Dictionary(_), CLType::Any) => match unwrap_dictionary_value(&cl_value) {
+            Some(inner) => inner,
+            None if T::cl_type() == CLType::Any => cl_value,
+            None => {
+                return Err(DecodeError::InvalidWrapper(
+                    address,
+                    bytesrepr::Error::Formatting,
+                ))
+            }
+        },
+        _ => cl_value,
+    };
+    cl_value
+        .into_t()
+        .map_err(|error| DecodeError::InvalidValue(address, error))
+}
+
+/// Split a stored dictionary value into the wrapped value, its seed URef address and its item key,
+/// returning the wrapped value if the bytes are exactly such a triple.
+fn unwrap_dictionary_value(cl_value: &CLValue) -> Option<CLValue> {
+    let (inner, remainder) = CLValue::from_bytes(cl_value.inner_bytes()).ok()?;
+    let (_seed_uref_addr, remainder) = Bytes::from_bytes(remainder).ok()?;
+    let (_item_key, remainder) = Bytes::from_bytes(remainder).ok()?;
+    if remainder.is_empty() {
+        Some(inner)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,89 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::String, vec};
+
+use casper_contract::{
+    contract_api::{
+        runtime::{self, call_versioned_contract, revert},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::NamedKeys, runtime_args, ApiError, CLType, CLTyped, ContractPackageHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, U256,
+};
+use kyc_proxy_client::names::{
+    self,
+    consumer::{self, args, entry_points},
+};
+
+/// Errors the consumer contract reverts with, as `ApiError::User` codes.
+#[repr(u16)]
+enum Error {
+    /// The proxy's answer differs from the expected result.
+    UnexpectedResult = 600,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// EntryPoint that asks the proxy whether `account` is KYC proved, as a contract consumer, and reverts
+/// unless the answer is `result`.
+#[no_mangle]
+pub extern "C" fn check() {
+    let kyc_proxy_package_hash: ContractPackageHash =
+        runtime::get_named_arg(args::KYC_PROXY_PACKAGE_HASH);
+    let account: Key = runtime::get_named_arg(args::ACCOUNT);
+    let result: bool = runtime::get_named_arg(args::RESULT);
+    let answer: bool = call_versioned_contract(
+        kyc_proxy_package_hash,
+        None,
+        names::entry_points::IS_KYC_PROVED,
+        runtime_args! {
+            names::args::ACCOUNT => account,
+            names::args::INDEX => Option::<U256>::None
+        },
+    );
+    if answer != result {
+        revert(Error::UnexpectedResult)
+    }
+}
+
+/// Installer of the consumer contract, storing its hashes under named keys prefixed with "name".
+#[no_mangle]
+pub extern "C" fn call() {
+    let (contract_package_hash, _) = storage::create_contract_package_at_hash();
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::CHECK,
+        vec![
+            Parameter::new(args::KYC_PROXY_PACKAGE_HASH, ContractPackageHash::cl_type()),
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::RESULT, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let consumer_name: String = runtime::get_named_arg(args::CONSUMER_NAME);
+
+    let (contract_hash, _) =
+        storage::add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
+    runtime::put_key(
+        &consumer::package_hash_key(&consumer_name),
+        storage::new_uref(contract_package_hash).into(),
+    );
+    runtime::put_key(
+        &consumer::contract_hash_key(&consumer_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
@@ -0,0 +1,16 @@
+//! Dictionary item keys of values that have no string form of their own, shared by the providers.
+
+use alloc::{format, string::String};
+
+use casper_contract::contract_api::runtime;
+
+/// Hex encoded blake2b digest of serialized item key parts, such as a `Key` and an `Option<U256>`
+/// index that have no string form of their own to join. The digest has the same 64 characters
+/// for every key variant and index, well within the 128 characters allowed for item keys.
+pub fn key_digest(bytes: &[u8]) -> String {
+    let mut digest = String::with_capacity(64);
+    for byte in runtime::blake2b(bytes).iter() {
+        digest.push_str(&format!("{:02x}", byte));
+    }
+    digest
+}
@@ -0,0 +1,217 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+mod digest;
+
+use alloc::{boxed::Box, string::String, vec};
+
+use casper_contract::{
+    contract_api::{
+        runtime::{self, call_versioned_contract, revert},
+        storage::{self, dictionary_get, dictionary_put, new_dictionary},
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::ToBytes, contracts::NamedKeys, runtime_args, ApiError, CLType, CLTyped, CLValue,
+    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, URef,
+    U256,
+};
+use kyc_proxy_client::{
+    names::{
+        entry_points::IS_KYC_PROVED,
+        mock_provider::{args, entry_points, named_keys},
+        provider, token,
+    },
+    MockAnswer,
+};
+
+use crate::digest::key_digest;
+
+/// Errors the mock provider reverts with, as `ApiError::User` codes.
+#[repr(u16)]
+enum Error {
+    /// The account's scripted answer is `MockAnswer::Revert`.
+    ScriptedRevert = 500,
+    /// The answer code is not a `MockAnswer`.
+    InvalidAnswer = 501,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// EntryPoint that creates the answer dictionary and answers `false` by default.
+#[no_mangle]
+pub extern "C" fn init() {
+    new_dictionary(named_keys::MOCK_ANSWERS).unwrap_or_revert();
+    runtime::put_key(
+        named_keys::MOCK_DEFAULT_ANSWER,
+        storage::new_uref(MockAnswer::False as u8).into(),
+    );
+}
+
+/// EntryPoint answering as scripted for `account`, or with the default answer. The index is ignored.
+#[no_mangle]
+pub extern "C" fn is_kyc_proved() {
+    let account: Key = runtime::get_named_arg(args::ACCOUNT);
+    let ret: bool = approves(&account);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint answering like the CEP-78 `balance_of` of a KYC token: a balance of 1 for the
+/// accounts `is_kyc_proved` approves, and 0 for the others.
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let token_owner: Key = runtime::get_named_arg(token::args::CEP78_TOKEN_OWNER);
+    let ret: u64 = approves(&token_owner).into();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that scripts the answer for `account`.
+#[no_mangle]
+pub extern "C" fn set_answer() {
+    let account: Key = runtime::get_named_arg(args::ACCOUNT);
+    dictionary_put(
+        answers_uref(),
+        &key_digest(&account.to_bytes().unwrap_or_revert()),
+        answer_arg(),
+    );
+}
+
+/// EntryPoint that sets the answer for accounts without a scripted answer.
+#[no_mangle]
+pub extern "C" fn set_default_answer() {
+    storage::write(default_answer_uref(), answer_arg());
+}
+
+/// Installer of the mock provider, storing its hashes under the same named keys as `kyc-provider`.
+#[no_mangle]
+pub extern "C" fn call() {
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::INIT,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        IS_KYC_PROVED,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(
+                provider::args::INDEX,
+                CLType::Option(Box::new(U256::cl_type())),
+            ),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        token::entry_points::BALANCE_OF,
+        vec![Parameter::new(
+            token::args::CEP78_TOKEN_OWNER,
+            Key::cl_type(),
+        )],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_ANSWER,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::ANSWER, CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_DEFAULT_ANSWER,
+        vec![Parameter::new(args::ANSWER, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let provider_name: String = runtime::get_named_arg(provider::args::PROVIDER_NAME);
+
+    let (contract_hash, _) =
+        storage::add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
+    runtime::put_key(
+        &provider::package_hash_key(&provider_name),
+        storage::new_uref(contract_package_hash).into(),
+    );
+    runtime::put_key(
+        &provider::contract_hash_key(&provider_name),
+        storage::new_uref(contract_hash).into(),
+    );
+    runtime::put_key(
+        &provider::access_token_key(&provider_name),
+        access_uref.into(),
+    );
+
+    call_versioned_contract(
+        contract_package_hash,
+        None,
+        entry_points::INIT,
+        runtime_args! {},
+    )
+}
+
+/// Whether `account` is approved as scripted, or by the default answer. Reverts or burns gas if
+/// that is the answer.
+fn approves(account: &Key) -> bool {
+    let scripted: Option<u8> = dictionary_get(
+        answers_uref(),
+        &key_digest(&account.to_bytes().unwrap_or_revert()),
+    )
+    .unwrap_or_revert();
+    let code = match scripted {
+        Some(code) => code,
+        None => storage::read(default_answer_uref())
+            .unwrap_or_revert()
+            .unwrap_or_revert(),
+    };
+    match MockAnswer::from_u8(code).unwrap_or_revert_with(Error::InvalidAnswer) {
+        MockAnswer::False => false,
+        MockAnswer::True => true,
+        MockAnswer::Revert => revert(Error::ScriptedRevert),
+        MockAnswer::BurnGas => loop {
+            runtime::get_blocktime();
+        },
+    }
+}
+
+/// Read the `answer` argument, reverting unless it is a `MockAnswer` code.
+fn answer_arg() -> u8 {
+    let code: u8 = runtime::get_named_arg(args::ANSWER);
+    MockAnswer::from_u8(code).unwrap_or_revert_with(Error::InvalidAnswer) as u8
+}
+
+fn answers_uref() -> URef {
+    *runtime::get_key(named_keys::MOCK_ANSWERS)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+fn default_answer_uref() -> URef {
+    *runtime::get_key(named_keys::MOCK_DEFAULT_ANSWER)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
@@ -0,0 +1,253 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+mod digest;
+
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{
+        runtime::{self, call_versioned_contract, revert},
+        storage::{self, dictionary_get, dictionary_put, new_dictionary},
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::ToBytes, contracts::NamedKeys, runtime_args, ApiError, CLType, CLTyped, CLValue,
+    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, URef,
+    U256,
+};
+use kyc_proxy_client::names::provider::{self, args, entry_points, named_keys};
+
+use crate::digest::key_digest;
+
+/// Errors the provider reverts with, as `ApiError::User` codes.
+#[repr(u16)]
+enum Error {
+    /// The caller is not an admin of the provider.
+    NotAdmin = 400,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Approval of an account for an index: whether it is approved, and the block time it expires at.
+type Approval = (bool, Option<u64>);
+
+/// EntryPoint that creates the approval and admin dictionaries and grants the initial admins.
+/// It can only be called once, as the dictionaries already exist on the second call.
+#[no_mangle]
+pub extern "C" fn init() {
+    new_dictionary(named_keys::KYC_APPROVALS).unwrap_or_revert();
+    let admins_uref = new_dictionary(named_keys::KYC_ADMINS).unwrap_or_revert();
+    for admin in runtime::get_named_arg::<Vec<Key>>(args::ADMINS) {
+        dictionary_put(
+            admins_uref,
+            &key_digest(&admin.to_bytes().unwrap_or_revert()),
+            true,
+        );
+    }
+}
+
+/// EntryPoint that returns whether `account` holds an unexpired approval for `index`.
+/// Approvals for different indices, including `None`, are independent of each other.
+#[no_mangle]
+pub extern "C" fn is_kyc_proved() {
+    let account: Key = runtime::get_named_arg(args::ACCOUNT);
+    let index: Option<U256> = runtime::get_named_arg(args::INDEX);
+    let approval: Option<Approval> = dictionary_get(
+        dictionary_uref(named_keys::KYC_APPROVALS),
+        &approval_key(&account, index),
+    )
+    .unwrap_or_revert();
+    let proved = match approval {
+        Some((true, None)) => true,
+        Some((true, Some(expires_at))) => u64::from(runtime::get_blocktime()) < expires_at,
+        _ => false,
+    };
+    runtime::ret(CLValue::from_t(proved).unwrap_or_revert())
+}
+
+/// EntryPoint that approves `account` for `index`, replacing a previous approval.
+/// The approval stops proving the account at block time `expires_at` if given. Admins only.
+#[no_mangle]
+pub extern "C" fn approve() {
+    require_admin();
+    let account: Key = runtime::get_named_arg(args::ACCOUNT);
+    let index: Option<U256> = runtime::get_named_arg(args::INDEX);
+    let expires_at: Option<u64> = runtime::get_named_arg(args::EXPIRES_AT);
+    let approval: Approval = (true, expires_at);
+    dictionary_put(
+        dictionary_uref(named_keys::KYC_APPROVALS),
+        &approval_key(&account, index),
+        approval,
+    );
+}
+
+/// EntryPoint that revokes the approval of `account` for `index`. Admins only.
+#[no_mangle]
+pub extern "C" fn revoke() {
+    require_admin();
+    let account: Key = runtime::get_named_arg(args::ACCOUNT);
+    let index: Option<U256> = runtime::get_named_arg(args::INDEX);
+    let approval: Approval = (false, None);
+    dictionary_put(
+        dictionary_uref(named_keys::KYC_APPROVALS),
+        &approval_key(&account, index),
+        approval,
+    );
+}
+
+/// EntryPoint that grants approval rights to `admin`. Admins only.
+#[no_mangle]
+pub extern "C" fn add_admin() {
+    set_admin(true)
+}
+
+/// EntryPoint that withdraws the approval rights of `admin`. Admins only.
+#[no_mangle]
+pub extern "C" fn remove_admin() {
+    set_admin(false)
+}
+
+/// Installer of the reference provider. The named keys of the installing account are prefixed with
+/// the `name` argument; `admins` defaults to the installing account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::INIT,
+        vec![Parameter::new(
+            args::ADMINS,
+            CLType::List(Box::new(Key::cl_type())),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::IS_KYC_PROVED,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::APPROVE,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+            Parameter::new(args::EXPIRES_AT, CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REVOKE,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ADD_ADMIN,
+        vec![Parameter::new(args::ADMIN, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REMOVE_ADMIN,
+        vec![Parameter::new(args::ADMIN, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    let provider_name: String = runtime::get_named_arg(args::PROVIDER_NAME);
+
+    let (contract_hash, _) =
+        storage::add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
+    runtime::put_key(
+        &provider::package_hash_key(&provider_name),
+        storage::new_uref(contract_package_hash).into(),
+    );
+    runtime::put_key(
+        &provider::contract_hash_key(&provider_name),
+        storage::new_uref(contract_hash).into(),
+    );
+    runtime::put_key(
+        &provider::access_token_key(&provider_name),
+        access_uref.into(),
+    );
+
+    let admins = match runtime::get_named_arg::<Option<Vec<Key>>>(args::ADMINS) {
+        Some(admins) => admins,
+        None => vec![Key::Account(runtime::get_caller())],
+    };
+
+    call_versioned_contract(
+        contract_package_hash,
+        None,
+        entry_points::INIT,
+        runtime_args! {
+            args::ADMINS => admins
+        },
+    )
+}
+
+fn set_admin(is_admin: bool) {
+    require_admin();
+    let admin: Key = runtime::get_named_arg(args::ADMIN);
+    dictionary_put(
+        dictionary_uref(named_keys::KYC_ADMINS),
+        &key_digest(&admin.to_bytes().unwrap_or_revert()),
+        is_admin,
+    );
+}
+
+/// Revert unless the account calling the entry point is an admin.
+fn require_admin() {
+    let caller = Key::Account(runtime::get_caller());
+    let is_admin: Option<bool> = dictionary_get(
+        dictionary_uref(named_keys::KYC_ADMINS),
+        &key_digest(&caller.to_bytes().unwrap_or_revert()),
+    )
+    .unwrap_or_revert();
+    if is_admin != Some(true) {
+        revert(Error::NotAdmin)
+    }
+}
+
+fn dictionary_uref(name: &str) -> URef {
+    *runtime::get_key(name)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert()
+}
+
+/// Dictionary item key of the approval of `account` for `index`.
+fn approval_key(account: &Key, index: Option<U256>) -> String {
+    let mut bytes = account.to_bytes().unwrap_or_revert();
+    bytes.append(&mut index.to_bytes().unwrap_or_revert());
+    key_digest(&bytes)
+}
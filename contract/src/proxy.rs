@@ -3,138 +3,1170 @@
 
 extern crate alloc;
 
-use alloc::{
-    boxed::Box,
-    string::{String, ToString},
-    vec::Vec,
-    {format, vec},
-};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
 use casper_contract::{
     contract_api::{
-        runtime::{self, call_versioned_contract, revert},
+        self,
+        runtime::{self, call_contract, call_versioned_contract, revert},
         storage::{self, dictionary_get, dictionary_put, new_dictionary},
     },
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    contracts::NamedKeys, runtime_args, ApiError, CLType, CLTyped, CLValue, ContractPackageHash,
-    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, URef,
+    account::AccountHash,
+    api_error,
+    bytesrepr::{self, FromBytes, ToBytes},
+    contracts::NamedKeys,
+    runtime_args,
+    system::CallStackElement,
+    ApiError, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, PublicKey, RuntimeArgs, URef,
     U256,
 };
+use kyc_proxy_core::{
+    consumer_package_hash,
+    names::{self, args, entry_points},
+    provider_package_hash, public_key_account, AccountNormalization, ActiveProvider, Consumer,
+    ConsumerProfile, Error, ProviderAdapter, ProviderAddressing, ProviderCaller, ProviderHash,
+    ProviderMetadata, ProviderMetadataUpdate, ProviderStatus, ProviderStore, ProviderUsage,
+    ProxyChain, Registry, ReturnKind,
+};
+
+/// `ProviderStore` keeping the registry under the contract's named keys.
+struct HostStore;
+
+impl HostStore {
+    fn uref(name: &str) -> URef {
+        *runtime::get_key(name)
+            .unwrap_or_revert()
+            .as_uref()
+            .unwrap_or_revert()
+    }
+}
+
+impl ProviderStore for HostStore {
+    fn new_dictionary(&mut self, name: &str) {
+        new_dictionary(name).unwrap_or_revert();
+    }
+
+    fn dictionary_get<T: CLTyped + FromBytes>(&self, name: &str, key: &str) -> Option<T> {
+        dictionary_get(Self::uref(name), key).unwrap_or_revert()
+    }
+
+    fn dictionary_put<T: CLTyped + ToBytes>(&mut self, name: &str, key: &str, value: T) {
+        dictionary_put(Self::uref(name), key, value)
+    }
+
+    fn read<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T> {
+        let uref = *runtime::get_key(name)?.as_uref().unwrap_or_revert();
+        storage::read(uref).unwrap_or_revert()
+    }
+
+    fn write<T: CLTyped + ToBytes>(&mut self, name: &str, value: T) {
+        match runtime::get_key(name) {
+            Some(key) => storage::write(*key.as_uref().unwrap_or_revert(), value),
+            None => runtime::put_key(name, storage::new_uref(value).into()),
+        }
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        runtime::has_key(name)
+    }
+
+    fn remove(&mut self, name: &str) {
+        runtime::remove_key(name)
+    }
+}
+
+/// `ProviderCaller` calling providers through their adapter, at the latest version of their package
+/// or at their contract hash.
+struct HostCaller;
+
+impl ProviderCaller for HostCaller {
+    fn is_kyc_proved(
+        &mut self,
+        provider: &ActiveProvider,
+        account: Key,
+        index: Option<U256>,
+        chain: Option<&ProxyChain>,
+    ) -> bool {
+        let call_spec = provider.adapter.call_spec();
+        let mut call_args = RuntimeArgs::new();
+        call_args
+            .insert(call_spec.account_arg.as_str(), account)
+            .unwrap_or_revert();
+        if let Some(index_arg) = &call_spec.index_arg {
+            call_args
+                .insert(index_arg.as_str(), index)
+                .unwrap_or_revert();
+        }
+        if let Some(chain) = chain {
+            call_args
+                .insert(args::PROXY_HOPS, chain.hops)
+                .unwrap_or_revert();
+            call_args
+                .insert(args::VISITED_PROXIES, chain.visited.clone())
+                .unwrap_or_revert();
+        }
+        let entry_point = call_spec.entry_point.as_str();
+        match call_spec.returns {
+            ReturnKind::Bool => call_provider(provider, entry_point, call_args),
+            ReturnKind::PositiveU256 => {
+                let balance: U256 = call_provider(provider, entry_point, call_args);
+                !balance.is_zero()
+            }
+            ReturnKind::PositiveU64 => {
+                let balance: u64 = call_provider(provider, entry_point, call_args);
+                balance > 0
+            }
+        }
+    }
+}
+
+/// Call an entry point of a provider the way its addressing says.
+fn call_provider<T: CLTyped + FromBytes>(
+    provider: &ActiveProvider,
+    entry_point: &str,
+    call_args: RuntimeArgs,
+) -> T {
+    match provider.hash {
+        ProviderHash::Package(package_hash) => {
+            call_versioned_contract(package_hash, None, entry_point, call_args)
+        }
+        ProviderHash::Contract(contract_hash) => {
+            call_contract(contract_hash, entry_point, call_args)
+        }
+    }
+}
+
+/// Open the registry of this proxy at the current block time, reverting if its storage needs to be migrated first.
+fn open_registry() -> Registry<HostStore> {
+    Registry::open(HostStore, own_package_hash())
+        .unwrap_or_revert()
+        .with_blocktime(runtime::get_blocktime().into())
+}
+
+/// Open the registry like `open_registry`, reverting unless the account sending the deploy is an admin.
+fn open_registry_as_admin() -> Registry<HostStore> {
+    let registry = open_registry();
+    registry
+        .require_admin(runtime::get_caller())
+        .unwrap_or_revert();
+    registry
+}
+
+/// EntryPoint that initializes the contract's storage scheme. This entry_point can only be called once,
+/// as on the second call the creation of the dictionary will fail because it already exists.
+/// The account sending the deploy, which is the installer, becomes the first admin.
+#[no_mangle]
+pub extern "C" fn init() {
+    Registry::init(
+        HostStore,
+        own_package_hash(),
+        runtime::get_caller(),
+        runtime::get_named_arg(args::INITIAL_PROVIDERS),
+        new_registered_metadata(),
+    )
+    .unwrap_or_revert();
+}
+
+/// EntryPoint that checks with each stored KYC provider contract for an account's validity.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+/// Proxies that call this entry point on a nested proxy additionally pass the "proxy_hops" and "visited_proxies" arguments.
+#[no_mangle]
+pub extern "C" fn is_kyc_proved() {
+    let account = runtime::get_named_arg::<Key>(args::ACCOUNT);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let ret: bool = open_registry_for_check(&[account])
+        .is_kyc_proved(
+            &mut HostCaller,
+            account,
+            index,
+            &proxy_chain_from_named_args(),
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks with each stored KYC provider contract for the validity of the account whose
+/// main key is the given public key.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn is_kyc_proved_for_public_key() {
+    let public_key = runtime::get_named_arg::<PublicKey>(args::PUBLIC_KEY);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let account = public_key_account(&public_key);
+    let ret: bool = open_registry_for_check(&[account])
+        .is_kyc_proved(&mut HostCaller, account, index, &ProxyChain::default())
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks with each stored KYC provider contract for the validity of the account sending the deploy.
+/// Unlike `is_kyc_proved`, it reveals nothing about other accounts, so privacy mode never restricts it,
+/// while restricted mode does.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn am_i_kyc_proved() {
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let account = Key::Account(runtime::get_caller());
+    let ret: bool = open_registry_for_check(&[account])
+        .is_kyc_proved(&mut HostCaller, account, index, &ProxyChain::default())
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks an account's validity only with the stored KYC provider contracts that cover
+/// at least one of the requested jurisdictions.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn is_kyc_proved_in() {
+    let account = runtime::get_named_arg::<Key>(args::ACCOUNT);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let jurisdictions = runtime::get_named_arg::<Vec<String>>(args::JURISDICTIONS);
+    let ret: bool = open_registry_for_check(&[account])
+        .is_kyc_proved_in(
+            &mut HostCaller,
+            account,
+            index,
+            jurisdictions,
+            &proxy_chain_from_named_args(),
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks an account's validity only with the requested KYC provider contracts, skipping those
+/// that are banned or suspended. Reverts if none of the requested providers is stored.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn is_kyc_proved_with() {
+    let account = runtime::get_named_arg::<Key>(args::ACCOUNT);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let providers = runtime::get_named_arg::<Vec<ContractPackageHash>>(args::PROVIDERS);
+    let ret: bool = open_registry_for_check(&[account])
+        .is_kyc_proved_with(
+            &mut HostCaller,
+            account,
+            index,
+            providers,
+            &proxy_chain_from_named_args(),
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks the validity of many accounts, answering for each account in the given order.
+/// The stored providers are looked up once for the whole batch.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn are_kyc_proved() {
+    let accounts = runtime::get_named_arg::<Vec<Key>>(args::ACCOUNTS);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let ret: Vec<bool> = open_registry_for_check(&accounts)
+        .are_kyc_proved(
+            &mut HostCaller,
+            accounts,
+            index,
+            &proxy_chain_from_named_args(),
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks whether all given accounts are valid, stopping at the first invalid one.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn all_kyc_proved() {
+    let accounts = runtime::get_named_arg::<Vec<Key>>(args::ACCOUNTS);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let ret: bool = open_registry_for_check(&accounts)
+        .all_kyc_proved(
+            &mut HostCaller,
+            accounts,
+            index,
+            &proxy_chain_from_named_args(),
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that checks whether any of the given accounts is valid, stopping at the first valid one.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn any_kyc_proved() {
+    let accounts = runtime::get_named_arg::<Vec<Key>>(args::ACCOUNTS);
+    let index = runtime::get_named_arg::<Option<U256>>(args::INDEX);
+    let ret: bool = open_registry_for_check(&accounts)
+        .any_kyc_proved(
+            &mut HostCaller,
+            accounts,
+            index,
+            &proxy_chain_from_named_args(),
+        )
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Turn privacy mode on or off. In privacy mode, only consumer contracts given third-party access
+/// may check other accounts than the one sending the deploy. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_privacy_mode() {
+    open_registry_as_admin().set_privacy_mode(runtime::get_named_arg(args::ENABLED))
+}
+
+/// Give the "consumer" contract package third-party access, or take it away. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_third_party_access() {
+    open_registry_as_admin().set_third_party_access(
+        consumer_package_hash(runtime::get_named_arg(args::CONSUMER)).unwrap_or_revert(),
+        runtime::get_named_arg(args::ALLOWED),
+    )
+}
+
+/// EntryPoint that returns whether the "consumer" contract package has third-party access.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn has_third_party_access() {
+    let ret: bool = open_registry().has_third_party_access(
+        consumer_package_hash(runtime::get_named_arg(args::CONSUMER)).unwrap_or_revert(),
+    );
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Register the "consumer" contract package or account, so it may call the checks in restricted mode.
+/// Only admins may call it.
+#[no_mangle]
+pub extern "C" fn add_consumer() {
+    open_registry_as_admin().add_consumer(consumer_arg())
+}
+
+/// Unregister the "consumer" contract package or account. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn remove_consumer() {
+    open_registry_as_admin().remove_consumer(consumer_arg())
+}
+
+/// EntryPoint that returns whether the "consumer" contract package or account is registered.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn is_consumer() {
+    let ret: bool = open_registry().is_consumer(consumer_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Turn restricted mode on or off. In restricted mode, only registered consumers may call the checks.
+/// Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_restricted_mode() {
+    open_registry_as_admin().set_restricted_mode(runtime::get_named_arg(args::ENABLED))
+}
+
+/// Attach a profile to the "consumer" contract package, replacing the global configuration for its checks.
+/// The optional "providers" argument restricts them to these providers, the optional "threshold" argument sets
+/// how many providers have to approve an account, 1 by default, and the optional "min_tier" argument skips
+/// providers of a lower tier, 0 by default. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_consumer_profile() {
+    let profile = ConsumerProfile {
+        providers: get_optional_named_arg(args::PROVIDERS),
+        threshold: get_optional_named_arg(args::THRESHOLD).unwrap_or(1),
+        min_tier: get_optional_named_arg(args::MIN_TIER).unwrap_or_default(),
+    };
+    open_registry_as_admin()
+        .set_consumer_profile(
+            consumer_package_hash(runtime::get_named_arg(args::CONSUMER)).unwrap_or_revert(),
+            profile,
+        )
+        .unwrap_or_revert();
+}
+
+/// Remove the profile of the "consumer" contract package, whose checks then follow the global configuration.
+/// Only admins may call it.
+#[no_mangle]
+pub extern "C" fn remove_consumer_profile() {
+    open_registry_as_admin().remove_consumer_profile(
+        consumer_package_hash(runtime::get_named_arg(args::CONSUMER)).unwrap_or_revert(),
+    )
+}
+
+/// EntryPoint that returns the profile of the "consumer" contract package, or `None` if it follows the global configuration.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_consumer_profile() {
+    let ret: Option<ConsumerProfile> = open_registry().get_consumer_profile(
+        consumer_package_hash(runtime::get_named_arg(args::CONSUMER)).unwrap_or_revert(),
+    );
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Turn usage metering on or off. While it is on, the checks count the calls of each consumer
+/// and the calls and approvals of each provider they ask. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_usage_metering() {
+    open_registry_as_admin().set_usage_metering(runtime::get_named_arg(args::ENABLED))
+}
+
+/// EntryPoint that returns the number of checks the "consumer" contract package or account called
+/// in the current billing period.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_consumer_usage_stats() {
+    let ret: u64 = open_registry().get_consumer_usage(consumer_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that returns how often a KYC provider contract was asked, and approved, in the current billing period.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_usage_stats() {
+    let ret: ProviderUsage = open_registry().get_provider_usage(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Start a new billing period, resetting all usage counters to 0. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn reset_usage() {
+    open_registry_as_admin().reset_usage()
+}
+
+/// Add a new entry to the list of KYC providers.
+/// The optional "name", "website", "contact" and "description" arguments are stored as the provider's metadata,
+/// The optional "is_proxy" argument marks the provider as another KYC proxy,
+/// the optional "adapter" argument sets how it is asked, calling its `is_kyc_proved` by default,
+/// the optional "addressing" argument registers the "provider" key as a contract hash instead of a package hash,
+/// and the optional "normalization" argument sets which account keys it is asked with, any by default.
+/// The optional arguments only apply to a provider that is not registered yet, a registered one is left unchanged.
+#[no_mangle]
+pub extern "C" fn add_kyc_provider() {
+    let mut registry = open_registry().with_own_contract_hash(own_contract_hash());
+    let provider = provider_arg();
+    let is_registered = registry.get_provider_status(provider).is_some();
+    registry
+        .add_kyc_provider(
+            provider,
+            new_registered_metadata(),
+            metadata_update_from_named_args(),
+        )
+        .unwrap_or_revert();
+    if is_registered {
+        return;
+    }
+    if let Some(is_proxy) = get_optional_named_arg(args::IS_PROXY) {
+        registry.set_provider_is_proxy(provider, is_proxy);
+    }
+    if let Some(adapter) = get_optional_named_arg(args::ADAPTER) {
+        registry
+            .set_provider_adapter(provider, adapter)
+            .unwrap_or_revert();
+    }
+    if let Some(addressing) = get_optional_named_arg(args::ADDRESSING) {
+        registry.set_provider_addressing(provider, addressing);
+    }
+    if let Some(normalization) = get_optional_named_arg(args::NORMALIZATION) {
+        registry.set_provider_normalization(provider, normalization);
+    }
+}
+
+/// Declare a KYC provider contract as not acceptable, for the optional "reason" argument.
+/// This provider will not be asked in any of the following validity checks.
+#[no_mangle]
+pub extern "C" fn ban_provider() {
+    open_registry().ban_provider(
+        provider_arg(),
+        get_optional_named_arg(args::REASON).unwrap_or_default(),
+    )
+}
+
+/// Restore a banned KYC provider contract's accessibility through the proxy.
+/// This provider will be asked for an account's validity during checks.
+#[no_mangle]
+pub extern "C" fn unban_provider() {
+    open_registry().unban_provider(provider_arg())
+}
+
+/// Set the status of a stored KYC provider contract.
+/// Suspended providers are asked again once the block time reaches the end of their suspension,
+/// deprecated providers are still asked. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_provider_status() {
+    open_registry_as_admin()
+        .set_provider_status(provider_arg(), runtime::get_named_arg(args::STATUS))
+}
+
+/// EntryPoint that returns the status of a KYC provider contract, or `None` if it is not stored.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_status() {
+    let ret: Option<ProviderStatus> = open_registry().get_provider_status(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Set the jurisdictions (ISO 3166 codes) a stored KYC provider contract is certified for.
+/// Replaces any previously set jurisdictions of the provider. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_provider_jurisdictions() {
+    open_registry_as_admin()
+        .set_provider_jurisdictions(provider_arg(), runtime::get_named_arg(args::JURISDICTIONS))
+}
+
+/// Mark a stored KYC provider contract as another KYC proxy, or unmark it.
+/// Nested proxies are called with the hop counter and visited set that protect against cycles.
+/// Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_provider_is_proxy() {
+    open_registry_as_admin()
+        .set_provider_is_proxy(provider_arg(), runtime::get_named_arg(args::IS_PROXY))
+}
+
+/// Set the maximum number of nested proxies a check through this proxy may pass through.
+/// Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_max_proxy_depth() {
+    open_registry_as_admin().set_max_proxy_depth(runtime::get_named_arg::<u8>(args::MAX_DEPTH))
+}
+
+/// Overwrite the metadata fields of a stored KYC provider contract that are passed as arguments.
+/// Fields whose argument is omitted keep their current value. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn update_provider_metadata() {
+    open_registry_as_admin()
+        .update_provider_metadata(provider_arg(), metadata_update_from_named_args())
+}
+
+/// EntryPoint that returns the metadata record of a KYC provider contract, or `None` if it is not stored.
+/// The record is typed as the tuple `((name, website, contact), (description, added_at, added_by))`.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_info() {
+    let ret: Option<ProviderMetadata> = open_registry().get_provider_info(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that returns the adapter a KYC provider contract is asked through, or `None` if it is not stored.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_adapter() {
+    let ret: Option<ProviderAdapter> = open_registry().get_provider_adapter(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that returns whether a KYC provider contract is called as a package or at a contract hash,
+/// or `None` if it is not stored.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_addressing() {
+    let ret: Option<ProviderAddressing> = open_registry().get_provider_addressing(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that returns which account keys a KYC provider contract is asked with, or `None` if it is not stored.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_normalization() {
+    let ret: Option<AccountNormalization> =
+        open_registry().get_provider_normalization(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Set the tier of a KYC provider contract, which consumer profiles can require a minimum of.
+/// Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_provider_tier() {
+    open_registry_as_admin().set_provider_tier(provider_arg(), runtime::get_named_arg(args::TIER))
+}
+
+/// EntryPoint that returns the tier of a KYC provider contract, or `None` if it is not stored.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_provider_tier() {
+    let ret: Option<u8> = open_registry().get_provider_tier(provider_arg());
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Propose to link the "secondary" account to the calling account, which has to be KYC proved.
+/// The link is made once the secondary account accepts it with `accept_link`.
+#[no_mangle]
+pub extern "C" fn propose_link() {
+    open_registry()
+        .propose_link(
+            &mut HostCaller,
+            runtime::get_caller(),
+            account_hash_arg(args::SECONDARY),
+        )
+        .unwrap_or_revert()
+}
+
+/// Accept the link to the "primary" account proposed to the calling account.
+/// The calling account is then KYC proved whenever the primary account is.
+#[no_mangle]
+pub extern "C" fn accept_link() {
+    open_registry()
+        .accept_link(runtime::get_caller(), account_hash_arg(args::PRIMARY))
+        .unwrap_or_revert()
+}
+
+/// Remove the link between the calling account and the "account" argument, from either side of it.
+#[no_mangle]
+pub extern "C" fn unlink() {
+    open_registry()
+        .unlink(runtime::get_caller(), account_hash_arg(args::ACCOUNT))
+        .unwrap_or_revert()
+}
+
+/// Set the maximum number of secondary accounts linked to one primary account. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn set_max_links_per_primary() {
+    open_registry_as_admin()
+        .set_max_links_per_primary(runtime::get_named_arg::<u32>(args::MAX_LINKS))
+}
+
+/// EntryPoint that returns the primary account the "account" argument is linked to, if any.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_primary_account() {
+    let ret: Option<AccountHash> =
+        open_registry().get_primary_account(account_hash_arg(args::ACCOUNT));
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// EntryPoint that returns the secondary accounts linked to the "account" argument.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn get_linked_accounts() {
+    let ret: Vec<AccountHash> =
+        open_registry().get_linked_accounts(account_hash_arg(args::ACCOUNT));
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Grant the "admin" account admin rights over the proxy. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn add_admin() {
+    open_registry_as_admin().set_admin(account_hash_arg(args::ADMIN), true)
+}
+
+/// Revoke the admin rights of the "admin" account. Only admins may call it.
+#[no_mangle]
+pub extern "C" fn remove_admin() {
+    open_registry_as_admin().set_admin(account_hash_arg(args::ADMIN), false)
+}
+
+/// EntryPoint that returns whether the "admin" account is an admin of the proxy.
+/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
+#[no_mangle]
+pub extern "C" fn is_admin() {
+    let ret: bool = open_registry().is_admin(account_hash_arg(args::ADMIN));
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
+}
+
+/// Convert the storage of a proxy installed with an older storage schema to the current one.
+/// Storage that is already current is left unchanged. The account sending the deploy becomes
+/// the first admin, and is recorded as having registered the migrated providers, which is why the
+/// installer calls it in the deploy that upgrades the proxy.
+#[no_mangle]
+pub extern "C" fn migrate_storage() {
+    Registry::migrate(
+        HostStore,
+        own_package_hash(),
+        runtime::get_caller(),
+        new_registered_metadata(),
+    )
+    .unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::INIT,
+        vec![Parameter::new(
+            args::INITIAL_PROVIDERS,
+            CLType::List(Box::new(ContractPackageHash::cl_type())),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::IS_KYC_PROVED,
+        vec![],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::IS_KYC_PROVED_FOR_PUBLIC_KEY,
+        vec![
+            Parameter::new(args::PUBLIC_KEY, PublicKey::cl_type()),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::AM_I_KYC_PROVED,
+        vec![Parameter::new(
+            args::INDEX,
+            CLType::Option(Box::new(U256::cl_type())),
+        )],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::IS_KYC_PROVED_WITH,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+            Parameter::new(
+                args::PROVIDERS,
+                CLType::List(Box::new(ContractPackageHash::cl_type())),
+            ),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::IS_KYC_PROVED_IN,
+        vec![
+            Parameter::new(args::ACCOUNT, Key::cl_type()),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+            Parameter::new(
+                args::JURISDICTIONS,
+                CLType::List(Box::new(String::cl_type())),
+            ),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ARE_KYC_PROVED,
+        vec![
+            Parameter::new(args::ACCOUNTS, CLType::List(Box::new(Key::cl_type()))),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        CLType::List(Box::new(CLType::Bool)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ALL_KYC_PROVED,
+        vec![
+            Parameter::new(args::ACCOUNTS, CLType::List(Box::new(Key::cl_type()))),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ANY_KYC_PROVED,
+        vec![
+            Parameter::new(args::ACCOUNTS, CLType::List(Box::new(Key::cl_type()))),
+            Parameter::new(args::INDEX, CLType::Option(Box::new(U256::cl_type()))),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ADD_KYC_PROVIDER,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(args::PROVIDER_NAME, CLType::String),
+            Parameter::new(args::WEBSITE, CLType::String),
+            Parameter::new(args::CONTACT, CLType::String),
+            Parameter::new(args::DESCRIPTION, CLType::String),
+            Parameter::new(args::IS_PROXY, CLType::Bool),
+            Parameter::new(args::ADAPTER, ProviderAdapter::cl_type()),
+            Parameter::new(args::ADDRESSING, ProviderAddressing::cl_type()),
+            Parameter::new(args::NORMALIZATION, AccountNormalization::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::BAN_PROVIDER,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(args::REASON, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::UNBAN_PROVIDER,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-/// EntryPoint that initializes the contract's storage scheme. This entry_point can only be called once,
-/// as on the second call the creation of the dictionary will fail because it already exists.
-#[no_mangle]
-pub extern "C" fn init() {
-    ProviderDict::init(runtime::get_named_arg("initial_providers"))
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_PROVIDER_STATUS,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(args::STATUS, ProviderStatus::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-/// EntryPoint that checks with each stored KYC provider contract for an account's validity.
-/// This EntryPoint can only be called from inside a contract or a session code since it returns a value.
-#[no_mangle]
-pub extern "C" fn is_kyc_proved() {
-    let account = runtime::get_named_arg::<Key>("account");
-    let index = runtime::get_named_arg::<Option<U256>>("index");
-    let ret: bool = ProviderDict::open().is_kyc_proved(account, index);
-    runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PROVIDER_STATUS,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Option(Box::new(ProviderStatus::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-/// Add a new entry to the list of KYC providers.
-#[no_mangle]
-pub extern "C" fn add_kyc_provider() {
-    ProviderDict::open().add_kyc_provider(runtime::get_named_arg("provider"))
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_PROVIDER_JURISDICTIONS,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(
+                args::JURISDICTIONS,
+                CLType::List(Box::new(String::cl_type())),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-/// Declare a KYC provider contract as not acceptable.
-/// This provider will not be asked in any of the following validity checks.
-#[no_mangle]
-pub extern "C" fn ban_provider() {
-    ProviderDict::open().ban_provider(runtime::get_named_arg("provider"))
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_PROVIDER_IS_PROXY,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(args::IS_PROXY, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-/// Restore a KYC provider contract's accessibility through the proxy.
-/// This provider will be asked for an account's validity during checks.
-#[no_mangle]
-pub extern "C" fn unban_provider() {
-    ProviderDict::open().unban_provider(runtime::get_named_arg("provider"))
-}
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_MAX_PROXY_DEPTH,
+        vec![Parameter::new(args::MAX_DEPTH, CLType::U8)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-#[no_mangle]
-pub extern "C" fn call() {
-    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
-    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::UPDATE_PROVIDER_METADATA,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(args::PROVIDER_NAME, CLType::String),
+            Parameter::new(args::WEBSITE, CLType::String),
+            Parameter::new(args::CONTACT, CLType::String),
+            Parameter::new(args::DESCRIPTION, CLType::String),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "init",
-        vec![Parameter::new(
-            "initial_providers",
-            CLType::List(Box::new(ContractPackageHash::cl_type())),
-        )],
+        entry_points::GET_PROVIDER_INFO,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Option(Box::new(ProviderMetadata::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PROVIDER_ADAPTER,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Option(Box::new(ProviderAdapter::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PROVIDER_ADDRESSING,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Option(Box::new(ProviderAddressing::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PROVIDER_NORMALIZATION,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Option(Box::new(AccountNormalization::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::PROPOSE_LINK,
+        vec![Parameter::new(args::SECONDARY, Key::cl_type())],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "is_kyc_proved",
-        vec![],
+        entry_points::ACCEPT_LINK,
+        vec![Parameter::new(args::PRIMARY, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::UNLINK,
+        vec![Parameter::new(args::ACCOUNT, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_MAX_LINKS_PER_PRIMARY,
+        vec![Parameter::new(args::MAX_LINKS, CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PRIMARY_ACCOUNT,
+        vec![Parameter::new(args::ACCOUNT, Key::cl_type())],
+        CLType::Option(Box::new(AccountHash::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_LINKED_ACCOUNTS,
+        vec![Parameter::new(args::ACCOUNT, Key::cl_type())],
+        CLType::List(Box::new(AccountHash::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_PRIVACY_MODE,
+        vec![Parameter::new(args::ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_THIRD_PARTY_ACCESS,
+        vec![
+            Parameter::new(args::CONSUMER, Key::cl_type()),
+            Parameter::new(args::ALLOWED, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::HAS_THIRD_PARTY_ACCESS,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ADD_CONSUMER,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REMOVE_CONSUMER,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::IS_CONSUMER,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
         CLType::Bool,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "add_kyc_provider",
-        vec![Parameter::new("provider", Key::cl_type())],
+        entry_points::SET_RESTRICTED_MODE,
+        vec![Parameter::new(args::ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_PROVIDER_TIER,
+        vec![
+            Parameter::new(args::PROVIDER, Key::cl_type()),
+            Parameter::new(args::TIER, CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PROVIDER_TIER,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        CLType::Option(Box::new(CLType::U8)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_CONSUMER_PROFILE,
+        vec![
+            Parameter::new(args::CONSUMER, Key::cl_type()),
+            Parameter::new(
+                args::PROVIDERS,
+                CLType::List(Box::new(ContractPackageHash::cl_type())),
+            ),
+            Parameter::new(args::THRESHOLD, CLType::U8),
+            Parameter::new(args::MIN_TIER, CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::REMOVE_CONSUMER_PROFILE,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_CONSUMER_PROFILE,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
+        CLType::Option(Box::new(ConsumerProfile::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::SET_USAGE_METERING,
+        vec![Parameter::new(args::ENABLED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_CONSUMER_USAGE_STATS,
+        vec![Parameter::new(args::CONSUMER, Key::cl_type())],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::GET_PROVIDER_USAGE_STATS,
+        vec![Parameter::new(args::PROVIDER, Key::cl_type())],
+        ProviderUsage::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::RESET_USAGE,
+        vec![],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::ADD_ADMIN,
+        vec![Parameter::new(args::ADMIN, Key::cl_type())],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "ban_provider",
-        vec![Parameter::new("provider", Key::cl_type())],
+        entry_points::REMOVE_ADMIN,
+        vec![Parameter::new(args::ADMIN, Key::cl_type())],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
     entry_points.add_entry_point(EntryPoint::new(
-        "unban_provider",
-        vec![Parameter::new("provider", Key::cl_type())],
+        entry_points::IS_ADMIN,
+        vec![Parameter::new(args::ADMIN, Key::cl_type())],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        entry_points::MIGRATE_STORAGE,
+        vec![],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
 
-    let proxy_name: String = runtime::get_named_arg("name");
+    let proxy_name: String = runtime::get_named_arg(args::PROXY_NAME);
+    if runtime::has_key(&names::access_token_key(&proxy_name)) {
+        upgrade(&proxy_name, entry_points)
+    } else {
+        install(&proxy_name, entry_points)
+    }
+}
 
+/// Create the proxy's package with its first contract version and initialize its storage.
+fn install(proxy_name: &str, entry_points: EntryPoints) {
+    let (contract_package_hash, access_uref) = storage::create_contract_package_at_hash();
     let mut named_keys = NamedKeys::new();
     named_keys.insert(
-        format!("{}-proxy_contract_package", proxy_name),
+        names::contract_package_key(proxy_name),
         storage::new_uref(contract_package_hash).into(),
     );
     let (contract_hash, _) =
         storage::add_contract_version(contract_package_hash, entry_points, named_keys);
     runtime::put_key(
-        &format!("{}-proxy_package_hash", proxy_name),
+        &names::package_hash_key(proxy_name),
         contract_package_hash.into(),
     );
-    runtime::put_key(
-        &format!("{}-proxy_contract", proxy_name),
-        contract_hash.into(),
-    );
-    runtime::put_key(
-        &format!("{}-proxy_access_token", proxy_name),
-        access_uref.into(),
-    );
-    runtime::put_key(
-        &format!("{}-proxy_contract_hash", proxy_name),
-        storage::new_uref(contract_hash).into(),
-    );
+    runtime::put_key(&names::access_token_key(proxy_name), access_uref.into());
+    put_contract_keys(proxy_name, contract_hash);
 
     let initial_providers =
-        match runtime::get_named_arg::<Option<Vec<ContractPackageHash>>>("initial_providers") {
+        match runtime::get_named_arg::<Option<Vec<ContractPackageHash>>>(args::INITIAL_PROVIDERS) {
             Some(providers) => providers,
             None => Vec::new(),
         };
@@ -142,124 +1174,169 @@ pub extern "C" fn call() {
     call_versioned_contract(
         contract_package_hash,
         None,
-        "init",
+        entry_points::INIT,
         runtime_args! {
-            "initial_providers" => initial_providers
+            args::INITIAL_PROVIDERS => initial_providers
         },
     )
 }
 
-struct ProviderDict {
-    uref: URef,
-    len: u64,
+/// Add a contract version to the package the installing account created under `proxy_name`,
+/// disable the previous version and migrate the storage, making the installer its first admin.
+/// The engine copies the named keys of the previous version into the new one, so the new version
+/// finds the storage of any earlier schema, and "initial_providers" is ignored.
+fn upgrade(proxy_name: &str, entry_points: EntryPoints) {
+    let contract_package_hash = runtime::get_key(&names::package_hash_key(proxy_name))
+        .and_then(Key::into_hash)
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert();
+    let previous_contract_hash = runtime::get_key(&names::contract_key(proxy_name))
+        .and_then(Key::into_hash)
+        .map(ContractHash::new)
+        .unwrap_or_revert();
+    let (contract_hash, _) =
+        storage::add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
+    storage::disable_contract_version(contract_package_hash, previous_contract_hash)
+        .unwrap_or_revert();
+    put_contract_keys(proxy_name, contract_hash);
+
+    call_versioned_contract(
+        contract_package_hash,
+        None,
+        entry_points::MIGRATE_STORAGE,
+        RuntimeArgs::new(),
+    )
 }
 
-impl ProviderDict {
-    /// Create a new URef that represents a seed for a dictionary partition of the global state and puts it under the "kyc_providers" named key.
-    /// It then stores initial_providers in the dictionary, while setting their status as validated.
-    fn init(initial_providers: Vec<ContractPackageHash>) {
-        let dict_uref = new_dictionary("kyc_providers").unwrap_or_revert();
-        for (provider_index, provider_package_hash) in initial_providers.iter().enumerate() {
-            dictionary_put(
-                dict_uref,
-                &provider_index.to_string(),
-                *provider_package_hash,
-            );
-            dictionary_put(dict_uref, &provider_package_hash.to_string(), true);
-        }
-        dictionary_put(dict_uref, "len", initial_providers.len() as u64);
-    }
+/// Store the hash of the proxy's current contract version under the installing account's named keys.
+fn put_contract_keys(proxy_name: &str, contract_hash: ContractHash) {
+    runtime::put_key(&names::contract_key(proxy_name), contract_hash.into());
+    runtime::put_key(
+        &names::contract_hash_key(proxy_name),
+        storage::new_uref(contract_hash).into(),
+    );
+}
 
-    /// Return a struct consisting of the URef stored under "kyc_providers" named key that holds the corresponding dictionary,
-    /// and the number of stored providers.
-    fn open() -> Self {
-        let uref = *runtime::get_key("kyc_providers")
-            .unwrap_or_revert()
-            .as_uref()
-            .unwrap_or_revert();
-        let len: u64 = dictionary_get(uref, "len")
-            .unwrap_or_revert()
-            .unwrap_or_revert();
-        ProviderDict { uref, len }
-    }
+/// Open the registry for a check of `accounts` with the profile of the calling consumer, reverting if
+/// it may not check them.
+fn open_registry_for_check(accounts: &[Key]) -> Registry<HostStore> {
+    let consumer = consumer();
+    let registry = open_registry().with_consumer(consumer);
+    registry
+        .authorize_check(runtime::get_caller(), consumer, accounts)
+        .unwrap_or_revert();
+    registry
+}
 
-    /// Store a new KYC provider in the dictionary and set their status as validated.
-    fn add_kyc_provider(&self, provider_key: Key) {
-        let provider_package_hash = Self::convert_provider_key(provider_key);
-        let str_provider = provider_package_hash.to_string();
-        if dictionary_get::<bool>(self.uref, &str_provider)
-            .unwrap_or_revert()
-            .is_none()
-        {
-            dictionary_put(self.uref, &self.len.to_string(), provider_package_hash);
-            dictionary_put(self.uref, &str_provider, true);
-            dictionary_put(self.uref, "len", self.len + 1);
+/// Return the immediate caller of this proxy, taken from the call stack.
+fn consumer() -> Consumer {
+    match runtime::get_call_stack().iter().rev().nth(1) {
+        Some(CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        }) => Consumer::Contract(*contract_package_hash),
+        Some(CallStackElement::Session { account_hash })
+        | Some(CallStackElement::StoredSession { account_hash, .. }) => {
+            Consumer::Account(*account_hash)
         }
+        None => revert(Error::InvalidCallStack),
     }
+}
 
-    /// Set a stored provider as invalid in the dictionary.
-    fn ban_provider(&self, provider_key: Key) {
-        let str_provider = Self::convert_provider_key(provider_key).to_string();
-        if let Some(true) = dictionary_get::<bool>(self.uref, &str_provider).unwrap_or_revert() {
-            dictionary_put(self.uref, &str_provider, false);
-        }
+/// Read the "consumer" argument, reverting unless it is a `Key::Account` or a `Key::Hash`.
+fn consumer_arg() -> Consumer {
+    Consumer::from_key(runtime::get_named_arg(args::CONSUMER)).unwrap_or_revert()
+}
+
+/// Read the "provider" argument, reverting unless it is a `Key::Hash`.
+fn provider_arg() -> ContractPackageHash {
+    provider_package_hash(runtime::get_named_arg(args::PROVIDER)).unwrap_or_revert()
+}
+
+/// Read an account argument, reverting unless it is a `Key::Account`.
+fn account_hash_arg(name: &str) -> AccountHash {
+    runtime::get_named_arg::<Key>(name)
+        .into_account()
+        .unwrap_or_revert_with(Error::InvalidAccountArgument)
+}
+
+/// Create the metadata record of a provider registered in the current deploy.
+fn new_registered_metadata() -> ProviderMetadata {
+    ProviderMetadata::new(
+        runtime::get_blocktime().into(),
+        Key::Account(runtime::get_caller()),
+    )
+}
+
+/// Read the optional metadata runtime arguments of the current call.
+fn metadata_update_from_named_args() -> ProviderMetadataUpdate {
+    ProviderMetadataUpdate {
+        name: get_optional_named_arg(args::PROVIDER_NAME),
+        website: get_optional_named_arg(args::WEBSITE),
+        contact: get_optional_named_arg(args::CONTACT),
+        description: get_optional_named_arg(args::DESCRIPTION),
     }
+}
 
-    /// Set a stored provider as validated in the dictionary.
-    fn unban_provider(&self, provider_key: Key) {
-        let str_provider = Self::convert_provider_key(provider_key).to_string();
-        if let Some(false) = dictionary_get::<bool>(self.uref, &str_provider).unwrap_or_revert() {
-            dictionary_put(self.uref, &str_provider, true);
-        }
+/// Read the chain from the optional "proxy_hops" and "visited_proxies" arguments.
+/// A check that was not forwarded by another proxy starts an empty chain.
+fn proxy_chain_from_named_args() -> ProxyChain {
+    ProxyChain {
+        hops: get_optional_named_arg(args::PROXY_HOPS).unwrap_or_default(),
+        visited: get_optional_named_arg(args::VISITED_PROXIES).unwrap_or_default(),
     }
+}
 
-    /// Convert provider `Key` to `ContractPackageHash`.
-    fn convert_provider_key(provider_key: Key) -> ContractPackageHash {
-        match provider_key {
-            Key::Hash(provider_hash) => ContractPackageHash::from(provider_hash),
-            _ => revert(ApiError::User(300)),
-        }
+/// Return the package hash of this proxy, taken from the call stack.
+fn own_package_hash() -> ContractPackageHash {
+    match runtime::get_call_stack().last().unwrap_or_revert() {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        } => *contract_package_hash,
+        _ => revert(Error::InvalidCallStack),
     }
+}
 
-    /// Check account validity by asking all stored KYC providers sequentially, and return true on the first confirmation.
-    /// If all available providers refused return false,
-    fn is_kyc_proved(&self, account: Key, index: Option<U256>) -> bool {
-        for provider_index in 0..=self.len {
-            // check if there is a provider stored at the index
-            if let Some(provider_package_hash) =
-                dictionary_get::<ContractPackageHash>(self.uref, &provider_index.to_string())
-                    .unwrap_or_revert()
-            {
-                // check whether the provider is banned (result is `false` bool)
-                if let Some(true) =
-                    dictionary_get::<bool>(self.uref, &provider_package_hash.to_string())
-                        .unwrap_or_revert()
-                {
-                    // return with true on the first provider that says they have approved the account
-                    if self.is_kyc_proved_single(provider_package_hash, account, index) {
-                        return true;
-                    }
-                }
-            }
-        }
-        false
+/// Return the hash of this proxy's contract, taken from the call stack.
+fn own_contract_hash() -> ContractHash {
+    match runtime::get_call_stack().last().unwrap_or_revert() {
+        CallStackElement::StoredContract { contract_hash, .. } => *contract_hash,
+        _ => revert(Error::InvalidCallStack),
     }
+}
 
-    /// Call the is_kyc_proved entry point on a provider.
-    fn is_kyc_proved_single(
-        &self,
-        provider_package_hash: ContractPackageHash,
-        account: Key,
-        index: Option<U256>,
-    ) -> bool {
-        call_versioned_contract(
-            provider_package_hash,
-            None,
-            "is_kyc_proved",
-            runtime_args! {
-                "account" => account,
-                "index" => index
-            },
+/// Read a named argument of the current call that the caller may omit.
+/// Returns `None` if the argument is missing and reverts if it cannot be parsed as `T`.
+fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
         )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(ApiError::MissingArgument) => return None,
+        Err(error) => revert(error),
     }
+    let arg_bytes = if arg_size > 0 {
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                data_non_null_ptr.as_ptr(),
+                arg_size,
+            )
+        };
+        let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        api_error::result_from(ret).unwrap_or_revert();
+        data
+    } else {
+        Vec::new()
+    };
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument))
 }
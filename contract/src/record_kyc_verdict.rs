@@ -0,0 +1,45 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use casper_contract::{
+    contract_api::{
+        runtime::{self, call_versioned_contract, get_named_arg},
+        storage,
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U256};
+use kyc_proxy_client::names::{args, entry_points, named_keys};
+
+// Since the `is_kyc_proved` entry point on the proxy returns data, an account can not read its answer
+// from a plain contract call. This session code asks the proxy and stores the answer, together with the
+// block time, as a `(bool, u64)` under a named key of the calling account, where it can be queried from
+// global state. An existing URef under that named key is overwritten.
+#[no_mangle]
+pub extern "C" fn call() {
+    let kyc_proxy_package_hash: ContractPackageHash = get_named_arg(args::KYC_PROXY_PACKAGE_HASH);
+    let account = get_named_arg::<Option<Key>>(args::ACCOUNT)
+        .unwrap_or_else(|| Key::Account(runtime::get_caller()));
+    let index = get_named_arg::<Option<U256>>(args::INDEX);
+    let named_key = get_named_arg::<Option<String>>(args::NAMED_KEY)
+        .unwrap_or_else(|| named_keys::KYC_VERDICT.to_string());
+
+    let verdict: bool = call_versioned_contract(
+        kyc_proxy_package_hash,
+        None,
+        entry_points::IS_KYC_PROVED,
+        runtime_args! {
+            args::ACCOUNT => account,
+            args::INDEX => index
+        },
+    );
+    let record = (verdict, u64::from(runtime::get_blocktime()));
+
+    match runtime::get_key(&named_key) {
+        Some(key) => storage::write(*key.as_uref().unwrap_or_revert(), record),
+        None => runtime::put_key(&named_key, storage::new_uref(record).into()),
+    }
+}
@@ -1,26 +1,68 @@
 #![no_main]
 #![no_std]
 
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
 use casper_contract::contract_api::runtime::{
     call_versioned_contract, get_caller, get_named_arg, revert,
 };
-use casper_types::{runtime_args, ApiError, Key, RuntimeArgs, U256};
+use casper_types::{
+    runtime_args, ApiError, ContractPackageHash, Key, PublicKey, RuntimeArgs, U256,
+};
+use kyc_proxy_client::names::{args, entry_points};
 
 // Since the `is_kyc_proved` entry point on the contract returns data, it can only be called from
 // another contract or session code. This requires the use of this session code in the tests.
 #[no_mangle]
 pub extern "C" fn call() {
-    if get_named_arg::<bool>("result")
-        != call_versioned_contract::<bool>(
-            get_named_arg("kyc_proxy_package_hash"),
-            None,
-            "is_kyc_proved",
-            runtime_args! {
-                "account" => Key::Account(get_caller()),
-                "index" => Option::<U256>::None
-            },
-        )
-    {
+    let entry_point: String = get_named_arg("entry_point");
+    let account = Key::Account(get_caller());
+    let index = Option::<U256>::None;
+    let args = match entry_point.as_str() {
+        entry_points::IS_KYC_PROVED => runtime_args! {
+            args::ACCOUNT => account,
+            args::INDEX => index
+        },
+        entry_points::IS_KYC_PROVED_FOR_PUBLIC_KEY => runtime_args! {
+            args::PUBLIC_KEY => get_named_arg::<PublicKey>(args::PUBLIC_KEY),
+            args::INDEX => index
+        },
+        entry_points::AM_I_KYC_PROVED => runtime_args! {
+            args::INDEX => index
+        },
+        entry_points::IS_KYC_PROVED_IN => runtime_args! {
+            args::ACCOUNT => account,
+            args::INDEX => index,
+            args::JURISDICTIONS => get_named_arg::<Vec<String>>(args::JURISDICTIONS)
+        },
+        entry_points::IS_KYC_PROVED_WITH => runtime_args! {
+            args::ACCOUNT => account,
+            args::INDEX => index,
+            args::PROVIDERS => get_named_arg::<Vec<ContractPackageHash>>(args::PROVIDERS)
+        },
+        entry_points::ARE_KYC_PROVED
+        | entry_points::ALL_KYC_PROVED
+        | entry_points::ANY_KYC_PROVED => runtime_args! {
+            args::ACCOUNTS => get_named_arg::<Vec<Key>>(args::ACCOUNTS),
+            args::INDEX => index
+        },
+        _ => revert(ApiError::User(998)),
+    };
+    let kyc_proxy_package_hash = get_named_arg(args::KYC_PROXY_PACKAGE_HASH);
+    let matches = if entry_point == entry_points::ARE_KYC_PROVED {
+        get_named_arg::<Vec<bool>>("result")
+            == call_versioned_contract::<Vec<bool>>(
+                kyc_proxy_package_hash,
+                None,
+                &entry_point,
+                args,
+            )
+    } else {
+        get_named_arg::<bool>("result")
+            == call_versioned_contract::<bool>(kyc_proxy_package_hash, None, &entry_point, args)
+    };
+    if !matches {
         revert(ApiError::User(999))
     }
 }
@@ -0,0 +1,216 @@
+//! Ways of asking a provider contract whether an account is KYC proved.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+use crate::{
+    error::Error,
+    names::{args, entry_points, token},
+};
+
+const KYC_PROVIDER_TAG: u8 = 0;
+const CEP47_TAG: u8 = 1;
+const CEP78_TAG: u8 = 2;
+const CUSTOM_TAG: u8 = 3;
+
+const BOOL_TAG: u8 = 0;
+const POSITIVE_U256_TAG: u8 = 1;
+const POSITIVE_U64_TAG: u8 = 2;
+
+/// How the proxy asks a registered provider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProviderAdapter {
+    /// Call `is_kyc_proved(account, index) -> bool`.
+    KycProvider,
+    /// Call the CEP-47 `balance_of(owner) -> U256` of a KYC token, approving a non-zero balance.
+    Cep47,
+    /// Call the CEP-78 `balance_of(token_owner) -> u64` of a KYC token, approving a non-zero balance.
+    Cep78,
+    /// Call the entry point described by the `CallSpec`.
+    Custom(CallSpec),
+}
+
+impl ProviderAdapter {
+    /// The call the proxy makes to ask a provider through this adapter.
+    pub fn call_spec(&self) -> CallSpec {
+        match self {
+            ProviderAdapter::KycProvider => CallSpec {
+                entry_point: entry_points::IS_KYC_PROVED.to_string(),
+                account_arg: args::ACCOUNT.to_string(),
+                index_arg: Some(args::INDEX.to_string()),
+                returns: ReturnKind::Bool,
+            },
+            ProviderAdapter::Cep47 => CallSpec {
+                entry_point: token::entry_points::BALANCE_OF.to_string(),
+                account_arg: token::args::CEP47_OWNER.to_string(),
+                index_arg: None,
+                returns: ReturnKind::PositiveU256,
+            },
+            ProviderAdapter::Cep78 => CallSpec {
+                entry_point: token::entry_points::BALANCE_OF.to_string(),
+                account_arg: token::args::CEP78_TOKEN_OWNER.to_string(),
+                index_arg: None,
+                returns: ReturnKind::PositiveU64,
+            },
+            ProviderAdapter::Custom(call_spec) => call_spec.clone(),
+        }
+    }
+
+    /// Check that a custom call names an entry point and an account argument.
+    pub fn validate(&self) -> Result<(), Error> {
+        match self {
+            ProviderAdapter::Custom(call_spec)
+                if call_spec.entry_point.is_empty()
+                    || call_spec.account_arg.is_empty()
+                    || call_spec.index_arg.as_deref() == Some("") =>
+            {
+                Err(Error::InvalidCallSpec)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for ProviderAdapter {
+    fn default() -> Self {
+        ProviderAdapter::KycProvider
+    }
+}
+
+impl CLTyped for ProviderAdapter {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ProviderAdapter {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            ProviderAdapter::KycProvider => result.push(KYC_PROVIDER_TAG),
+            ProviderAdapter::Cep47 => result.push(CEP47_TAG),
+            ProviderAdapter::Cep78 => result.push(CEP78_TAG),
+            ProviderAdapter::Custom(call_spec) => {
+                result.push(CUSTOM_TAG);
+                result.append(&mut call_spec.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            ProviderAdapter::Custom(call_spec) => call_spec.serialized_length(),
+            _ => 0,
+        }
+    }
+}
+
+impl FromBytes for ProviderAdapter {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        match tag {
+            KYC_PROVIDER_TAG => Ok((ProviderAdapter::KycProvider, bytes)),
+            CEP47_TAG => Ok((ProviderAdapter::Cep47, bytes)),
+            CEP78_TAG => Ok((ProviderAdapter::Cep78, bytes)),
+            CUSTOM_TAG => {
+                let (call_spec, bytes) = CallSpec::from_bytes(bytes)?;
+                Ok((ProviderAdapter::Custom(call_spec), bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Entry point and argument names of a provider's check, and how its result approves an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallSpec {
+    pub entry_point: String,
+    /// Argument the account `Key` is passed as.
+    pub account_arg: String,
+    /// Argument the `Option<U256>` index is passed as, if the provider takes one.
+    pub index_arg: Option<String>,
+    pub returns: ReturnKind,
+}
+
+impl ToBytes for CallSpec {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.entry_point.to_bytes()?);
+        result.append(&mut self.account_arg.to_bytes()?);
+        result.append(&mut self.index_arg.to_bytes()?);
+        result.append(&mut self.returns.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.entry_point.serialized_length()
+            + self.account_arg.serialized_length()
+            + self.index_arg.serialized_length()
+            + self.returns.serialized_length()
+    }
+}
+
+impl FromBytes for CallSpec {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (entry_point, bytes) = String::from_bytes(bytes)?;
+        let (account_arg, bytes) = String::from_bytes(bytes)?;
+        let (index_arg, bytes) = Option::<String>::from_bytes(bytes)?;
+        let (returns, bytes) = ReturnKind::from_bytes(bytes)?;
+        Ok((
+            CallSpec {
+                entry_point,
+                account_arg,
+                index_arg,
+                returns,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// Return type of a provider's check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReturnKind {
+    /// A `bool`, approving on `true`.
+    Bool,
+    /// A `U256`, approving when greater than zero.
+    PositiveU256,
+    /// A `u64`, approving when greater than zero.
+    PositiveU64,
+}
+
+impl ToBytes for ReturnKind {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            ReturnKind::Bool => BOOL_TAG,
+            ReturnKind::PositiveU256 => POSITIVE_U256_TAG,
+            ReturnKind::PositiveU64 => POSITIVE_U64_TAG,
+        };
+        tag.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        1
+    }
+}
+
+impl FromBytes for ReturnKind {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        let returns = match tag {
+            BOOL_TAG => ReturnKind::Bool,
+            POSITIVE_U256_TAG => ReturnKind::PositiveU256,
+            POSITIVE_U64_TAG => ReturnKind::PositiveU64,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((returns, bytes))
+    }
+}
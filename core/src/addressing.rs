@@ -0,0 +1,87 @@
+//! How the proxy addresses a registered provider contract.
+
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, ContractHash, ContractPackageHash,
+};
+
+const PACKAGE_TAG: u8 = 0;
+const CONTRACT_TAG: u8 = 1;
+
+/// What the hash a provider is registered under points to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProviderAddressing {
+    /// A contract package, whose latest version is called through `call_versioned_contract`.
+    Package,
+    /// A contract, called at that fixed hash through `call_contract`.
+    Contract,
+}
+
+impl ProviderAddressing {
+    /// The hash a provider registered under `registered_hash` is called at with this addressing.
+    /// Providers are registered under the bytes of their `Key::Hash`, whichever hash these are.
+    pub fn provider_hash(self, registered_hash: ContractPackageHash) -> ProviderHash {
+        match self {
+            ProviderAddressing::Package => ProviderHash::Package(registered_hash),
+            ProviderAddressing::Contract => {
+                ProviderHash::Contract(ContractHash::new(registered_hash.value()))
+            }
+        }
+    }
+}
+
+impl Default for ProviderAddressing {
+    fn default() -> Self {
+        ProviderAddressing::Package
+    }
+}
+
+impl CLTyped for ProviderAddressing {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ProviderAddressing {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            ProviderAddressing::Package => PACKAGE_TAG,
+            ProviderAddressing::Contract => CONTRACT_TAG,
+        };
+        tag.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        1
+    }
+}
+
+impl FromBytes for ProviderAddressing {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        let addressing = match tag {
+            PACKAGE_TAG => ProviderAddressing::Package,
+            CONTRACT_TAG => ProviderAddressing::Contract,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((addressing, bytes))
+    }
+}
+
+/// Hash a provider is called at, typed by its addressing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProviderHash {
+    Package(ContractPackageHash),
+    Contract(ContractHash),
+}
+
+impl ProviderHash {
+    pub fn addressing(&self) -> ProviderAddressing {
+        match self {
+            ProviderHash::Package(_) => ProviderAddressing::Package,
+            ProviderHash::Contract(_) => ProviderAddressing::Contract,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Calls from the proxy to its providers.
+
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, ContractPackageHash, Key, U256,
+};
+
+use crate::{
+    adapter::ProviderAdapter,
+    addressing::{ProviderAddressing, ProviderHash},
+    chain::ProxyChain,
+    normalization::AccountNormalization,
+};
+
+/// An unbanned provider, as stored in the "kyc_active_providers" list.
+/// Its hash is stored with the tag of its addressing, and typed by it when the list is read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveProvider {
+    /// Hash the provider is registered under, whatever its addressing.
+    pub package_hash: ContractPackageHash,
+    pub is_proxy: bool,
+    /// Block time a suspended provider is asked again from.
+    pub suspended_until: Option<u64>,
+    pub adapter: ProviderAdapter,
+    /// Hash the provider is called at.
+    pub hash: ProviderHash,
+    pub normalization: AccountNormalization,
+    /// Tier set by the admins, compared with the minimum tier of consumer profiles.
+    pub tier: u8,
+}
+
+impl CLTyped for ActiveProvider {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ActiveProvider {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.package_hash.to_bytes()?);
+        result.append(&mut self.is_proxy.to_bytes()?);
+        result.append(&mut self.suspended_until.to_bytes()?);
+        result.append(&mut self.adapter.to_bytes()?);
+        result.append(&mut self.hash.addressing().to_bytes()?);
+        result.append(&mut self.normalization.to_bytes()?);
+        result.append(&mut self.tier.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.package_hash.serialized_length()
+            + self.is_proxy.serialized_length()
+            + self.suspended_until.serialized_length()
+            + self.adapter.serialized_length()
+            + self.hash.addressing().serialized_length()
+            + self.normalization.serialized_length()
+            + self.tier.serialized_length()
+    }
+}
+
+impl FromBytes for ActiveProvider {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (package_hash, bytes) = ContractPackageHash::from_bytes(bytes)?;
+        let (is_proxy, bytes) = bool::from_bytes(bytes)?;
+        let (suspended_until, bytes) = Option::<u64>::from_bytes(bytes)?;
+        let (adapter, bytes) = ProviderAdapter::from_bytes(bytes)?;
+        let (addressing, bytes) = ProviderAddressing::from_bytes(bytes)?;
+        let (normalization, bytes) = AccountNormalization::from_bytes(bytes)?;
+        let (tier, bytes) = u8::from_bytes(bytes)?;
+        Ok((
+            ActiveProvider {
+                package_hash,
+                is_proxy,
+                suspended_until,
+                adapter,
+                hash: addressing.provider_hash(package_hash),
+                normalization,
+                tier,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// Asks providers whether an account is KYC proved.
+pub trait ProviderCaller {
+    /// Ask `provider` through its adapter, calling its `is_kyc_proved` entry point by default,
+    /// at the package or contract its addressing says. `account` is already normalized for the provider.
+    /// Nested proxies are passed `chain`, the proxy chain extended with the calling proxy.
+    fn is_kyc_proved(
+        &mut self,
+        provider: &ActiveProvider,
+        account: Key,
+        index: Option<U256>,
+        chain: Option<&ProxyChain>,
+    ) -> bool;
+}
@@ -0,0 +1,39 @@
+//! Protection of checks passing through nested proxies.
+
+use alloc::vec::Vec;
+
+use casper_types::ContractPackageHash;
+
+/// Position of the current check in a chain of nested proxies, passed along through runtime arguments.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProxyChain {
+    /// Number of proxies the check passed through before reaching this one.
+    pub hops: u8,
+    /// Package hashes of the proxies the check passed through before reaching this one.
+    pub visited: Vec<ContractPackageHash>,
+}
+
+impl ProxyChain {
+    /// Check whether the check already passed through the proxy `own_package_hash`.
+    pub fn is_cycle(&self, own_package_hash: ContractPackageHash) -> bool {
+        self.visited.contains(&own_package_hash)
+    }
+
+    /// Return the chain extended with the proxy `own_package_hash`,
+    /// or `None` if forwarding would exceed `max_depth`.
+    pub fn next_hop(
+        &self,
+        own_package_hash: ContractPackageHash,
+        max_depth: u8,
+    ) -> Option<ProxyChain> {
+        if self.hops >= max_depth {
+            return None;
+        }
+        let mut visited = self.visited.clone();
+        visited.push(own_package_hash);
+        Some(ProxyChain {
+            hops: self.hops + 1,
+            visited,
+        })
+    }
+}
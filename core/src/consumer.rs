@@ -0,0 +1,35 @@
+//! The callers of the proxy's checks.
+
+use casper_types::{account::AccountHash, ContractPackageHash, Key};
+
+use crate::error::Error;
+
+/// The immediate caller of a check: the contract that called the proxy, or the account whose
+/// session code did.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Consumer {
+    Account(AccountHash),
+    Contract(ContractPackageHash),
+}
+
+impl Consumer {
+    /// Convert a consumer `Key` to a `Consumer`: a `Key::Account` to an account, a `Key::Hash` to
+    /// a contract package.
+    pub fn from_key(consumer_key: Key) -> Result<Consumer, Error> {
+        match consumer_key {
+            Key::Account(account_hash) => Ok(Consumer::Account(account_hash)),
+            Key::Hash(consumer_hash) => {
+                Ok(Consumer::Contract(ContractPackageHash::from(consumer_hash)))
+            }
+            _ => Err(Error::InvalidConsumerKey),
+        }
+    }
+}
+
+/// Convert a consumer contract `Key` to its `ContractPackageHash`.
+pub fn consumer_package_hash(consumer_key: Key) -> Result<ContractPackageHash, Error> {
+    match consumer_key {
+        Key::Hash(consumer_hash) => Ok(ContractPackageHash::from(consumer_hash)),
+        _ => Err(Error::InvalidConsumerKey),
+    }
+}
@@ -0,0 +1,52 @@
+//! Errors the proxy reverts with.
+
+use casper_types::ApiError;
+
+/// Errors the proxy reverts with, as `ApiError::User` codes.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The provider was not given as a `Key::Hash`.
+    InvalidProviderKey = 300,
+    /// The proxy was asked to register itself as a provider.
+    SelfReference = 301,
+    /// The proxy was not called as a stored contract.
+    InvalidCallStack = 302,
+    /// The storage uses an older schema, and needs to be converted with `migrate_storage`.
+    OutdatedSchema = 303,
+    /// The storage uses a schema that can not be migrated.
+    UnsupportedSchema = 304,
+    /// A custom provider call is missing its entry point or an argument name.
+    InvalidCallSpec = 305,
+    /// The account of a check is a `Key` variant that none of the providers to ask accepts.
+    UnsupportedAccountKey = 306,
+    /// A primary account proposed a link without being KYC proved itself.
+    PrimaryNotKycProved = 307,
+    /// The accounts can not be linked, or unlinked: they are the same account, one of them is
+    /// already in another link, or the link was not proposed or does not exist.
+    InvalidLink = 308,
+    /// The primary account already has the maximum number of linked accounts.
+    LinkLimitReached = 309,
+    /// Privacy mode is on, and a consumer without third-party access checked another account than the caller's.
+    ThirdPartyCheckDenied = 310,
+    /// The consumer was not given as a `Key::Hash`, or as a `Key::Account` where accounts are accepted.
+    InvalidConsumerKey = 311,
+    /// Restricted mode is on, and the consumer calling the proxy is not registered.
+    UnknownConsumer = 312,
+    /// A consumer profile has a threshold of 0.
+    InvalidConsumerProfile = 313,
+    /// None of the providers a check was restricted to is registered.
+    UnknownProviders = 314,
+    /// The account calling an admin entry point is not an admin of the proxy.
+    NotAdmin = 315,
+    /// The storage has a schema version but misses the provider count written by `init`.
+    UninitializedRegistry = 316,
+    /// An account argument of a link or admin entry point was not given as a `Key::Account`.
+    InvalidAccountArgument = 317,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
@@ -0,0 +1,41 @@
+//! Decision logic of the KYC proxy, independent of the Casper host.
+//!
+//! `Registry` keeps the registered providers in a `ProviderStore` and answers checks by asking them
+//! through a `ProviderCaller`. The contract implements both traits against the host functions; the
+//! `memory` module implements them in memory, so the logic is tested natively without the engine.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod adapter;
+pub mod addressing;
+pub mod caller;
+pub mod chain;
+pub mod consumer;
+pub mod error;
+pub mod memory;
+pub mod metadata;
+pub mod names;
+pub mod normalization;
+pub mod profile;
+pub mod record;
+pub mod registry;
+pub mod store;
+pub mod usage;
+
+pub use adapter::{CallSpec, ProviderAdapter, ReturnKind};
+pub use addressing::{ProviderAddressing, ProviderHash};
+pub use caller::{ActiveProvider, ProviderCaller};
+pub use chain::ProxyChain;
+pub use consumer::{consumer_package_hash, Consumer};
+pub use error::Error;
+pub use metadata::{ProviderMetadata, ProviderMetadataUpdate};
+pub use normalization::{public_key_account, AccountNormalization};
+pub use profile::ConsumerProfile;
+pub use record::{ProviderRecord, ProviderStatus};
+pub use registry::{
+    provider_package_hash, Registry, CURRENT_SCHEMA_VERSION, DEFAULT_MAX_LINKS_PER_PRIMARY,
+    DEFAULT_MAX_PROXY_DEPTH,
+};
+pub use store::ProviderStore;
+pub use usage::ProviderUsage;
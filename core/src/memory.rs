@@ -0,0 +1,141 @@
+//! In-memory implementations of `ProviderStore` and `ProviderCaller` for native tests.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLTyped, ContractPackageHash, Key, U256,
+};
+
+use crate::{
+    caller::{ActiveProvider, ProviderCaller},
+    chain::ProxyChain,
+    store::ProviderStore,
+};
+
+/// Dictionaries and values kept as serialized bytes, like global state does.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStore {
+    dictionaries: BTreeMap<String, BTreeMap<String, Vec<u8>>>,
+    values: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Item keys of the dictionary `name`, in lexicographic order.
+    pub fn dictionary_keys(&self, name: &str) -> Vec<String> {
+        self.dictionary(name).keys().cloned().collect()
+    }
+
+    fn dictionary(&self, name: &str) -> &BTreeMap<String, Vec<u8>> {
+        self.dictionaries
+            .get(name)
+            .unwrap_or_else(|| panic!("dictionary {} should exist", name))
+    }
+}
+
+impl ProviderStore for MemoryStore {
+    fn new_dictionary(&mut self, name: &str) {
+        if self.dictionaries.contains_key(name) {
+            panic!("dictionary {} already exists", name);
+        }
+        self.dictionaries.insert(name.to_string(), BTreeMap::new());
+    }
+
+    fn dictionary_get<T: CLTyped + FromBytes>(&self, name: &str, key: &str) -> Option<T> {
+        self.dictionary(name)
+            .get(key)
+            .map(|bytes| bytesrepr::deserialize(bytes.clone()).expect("should deserialize"))
+    }
+
+    fn dictionary_put<T: CLTyped + ToBytes>(&mut self, name: &str, key: &str, value: T) {
+        self.dictionaries
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("dictionary {} should exist", name))
+            .insert(key.to_string(), value.to_bytes().expect("should serialize"));
+    }
+
+    fn read<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T> {
+        self.values
+            .get(name)
+            .map(|bytes| bytesrepr::deserialize(bytes.clone()).expect("should deserialize"))
+    }
+
+    fn write<T: CLTyped + ToBytes>(&mut self, name: &str, value: T) {
+        self.values.insert(
+            name.to_string(),
+            value.to_bytes().expect("should serialize"),
+        );
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.dictionaries.contains_key(name) || self.values.contains_key(name)
+    }
+
+    fn remove(&mut self, name: &str) {
+        self.dictionaries.remove(name);
+        self.values.remove(name);
+    }
+}
+
+/// A call made through a `MemoryCaller`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedCall {
+    pub provider: ContractPackageHash,
+    pub account: Key,
+    pub index: Option<U256>,
+    pub chain: Option<ProxyChain>,
+}
+
+/// Providers answering from a fixed set of approvals, recording every call.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCaller {
+    approvals: BTreeSet<(ContractPackageHash, Key)>,
+    pub calls: Vec<RecordedCall>,
+}
+
+impl MemoryCaller {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let `provider` answer `true` for `account`, whatever the index.
+    pub fn approve(&mut self, provider: ContractPackageHash, account: Key) {
+        self.approvals.insert((provider, account));
+    }
+
+    /// Let `provider` answer `false` for `account` again.
+    pub fn revoke(&mut self, provider: ContractPackageHash, account: Key) {
+        self.approvals.remove(&(provider, account));
+    }
+
+    /// Providers asked so far, in call order.
+    pub fn asked_providers(&self) -> Vec<ContractPackageHash> {
+        self.calls.iter().map(|call| call.provider).collect()
+    }
+}
+
+impl ProviderCaller for MemoryCaller {
+    fn is_kyc_proved(
+        &mut self,
+        provider: &ActiveProvider,
+        account: Key,
+        index: Option<U256>,
+        chain: Option<&ProxyChain>,
+    ) -> bool {
+        self.calls.push(RecordedCall {
+            provider: provider.package_hash,
+            account,
+            index,
+            chain: chain.cloned(),
+        });
+        self.approvals.contains(&(provider.package_hash, account))
+    }
+}
@@ -0,0 +1,114 @@
+//! Descriptive records of the registered providers.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key,
+};
+
+/// Descriptive record of a KYC provider, stored alongside its status entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProviderMetadata {
+    pub name: String,
+    pub website: String,
+    pub contact: String,
+    pub description: String,
+    /// Block time of the provider's registration.
+    pub added_at: u64,
+    /// Account that registered the provider.
+    pub added_by: Key,
+}
+
+impl ProviderMetadata {
+    /// Create the empty record of a provider registered by `added_by` at block time `added_at`.
+    pub fn new(added_at: u64, added_by: Key) -> Self {
+        ProviderMetadata {
+            name: String::new(),
+            website: String::new(),
+            contact: String::new(),
+            description: String::new(),
+            added_at,
+            added_by,
+        }
+    }
+}
+
+/// The record is serialized like `((name, website, contact), (description, added_at, added_by))`
+/// would be, so it is typed as that tuple, `CLType` having no tuple of more than three elements.
+impl CLTyped for ProviderMetadata {
+    fn cl_type() -> CLType {
+        <((String, String, String), (String, u64, Key))>::cl_type()
+    }
+}
+
+impl ToBytes for ProviderMetadata {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.name.to_bytes()?);
+        result.append(&mut self.website.to_bytes()?);
+        result.append(&mut self.contact.to_bytes()?);
+        result.append(&mut self.description.to_bytes()?);
+        result.append(&mut self.added_at.to_bytes()?);
+        result.append(&mut self.added_by.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length()
+            + self.website.serialized_length()
+            + self.contact.serialized_length()
+            + self.description.serialized_length()
+            + self.added_at.serialized_length()
+            + self.added_by.serialized_length()
+    }
+}
+
+impl FromBytes for ProviderMetadata {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, bytes) = String::from_bytes(bytes)?;
+        let (website, bytes) = String::from_bytes(bytes)?;
+        let (contact, bytes) = String::from_bytes(bytes)?;
+        let (description, bytes) = String::from_bytes(bytes)?;
+        let (added_at, bytes) = u64::from_bytes(bytes)?;
+        let (added_by, bytes) = Key::from_bytes(bytes)?;
+        Ok((
+            ProviderMetadata {
+                name,
+                website,
+                contact,
+                description,
+                added_at,
+                added_by,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// Metadata fields passed to `add_kyc_provider` or `update_provider_metadata`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProviderMetadataUpdate {
+    pub name: Option<String>,
+    pub website: Option<String>,
+    pub contact: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ProviderMetadataUpdate {
+    /// Overwrite the fields of `metadata` that are set in this update.
+    pub fn apply(self, metadata: &mut ProviderMetadata) {
+        if let Some(name) = self.name {
+            metadata.name = name;
+        }
+        if let Some(website) = self.website {
+            metadata.website = website;
+        }
+        if let Some(contact) = self.contact {
+            metadata.contact = contact;
+        }
+        if let Some(description) = self.description {
+            metadata.description = description;
+        }
+    }
+}
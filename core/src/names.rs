@@ -0,0 +1,308 @@
+//! Names of the KYC proxy's entry points, runtime arguments and named keys.
+//! The contract and its clients both use these, so a name is only ever spelled once.
+
+use alloc::{format, string::String};
+
+/// Entry points of the installed proxy contract.
+pub mod entry_points {
+    pub const INIT: &str = "init";
+    pub const IS_KYC_PROVED: &str = "is_kyc_proved";
+    pub const IS_KYC_PROVED_IN: &str = "is_kyc_proved_in";
+    pub const IS_KYC_PROVED_WITH: &str = "is_kyc_proved_with";
+    pub const IS_KYC_PROVED_FOR_PUBLIC_KEY: &str = "is_kyc_proved_for_public_key";
+    pub const AM_I_KYC_PROVED: &str = "am_i_kyc_proved";
+    pub const ARE_KYC_PROVED: &str = "are_kyc_proved";
+    pub const ALL_KYC_PROVED: &str = "all_kyc_proved";
+    pub const ANY_KYC_PROVED: &str = "any_kyc_proved";
+    pub const ADD_KYC_PROVIDER: &str = "add_kyc_provider";
+    pub const BAN_PROVIDER: &str = "ban_provider";
+    pub const UNBAN_PROVIDER: &str = "unban_provider";
+    pub const SET_PROVIDER_STATUS: &str = "set_provider_status";
+    pub const GET_PROVIDER_STATUS: &str = "get_provider_status";
+    pub const SET_PROVIDER_JURISDICTIONS: &str = "set_provider_jurisdictions";
+    pub const SET_PROVIDER_IS_PROXY: &str = "set_provider_is_proxy";
+    pub const SET_MAX_PROXY_DEPTH: &str = "set_max_proxy_depth";
+    pub const UPDATE_PROVIDER_METADATA: &str = "update_provider_metadata";
+    pub const GET_PROVIDER_INFO: &str = "get_provider_info";
+    pub const GET_PROVIDER_ADAPTER: &str = "get_provider_adapter";
+    pub const GET_PROVIDER_ADDRESSING: &str = "get_provider_addressing";
+    pub const GET_PROVIDER_NORMALIZATION: &str = "get_provider_normalization";
+    pub const PROPOSE_LINK: &str = "propose_link";
+    pub const ACCEPT_LINK: &str = "accept_link";
+    pub const UNLINK: &str = "unlink";
+    pub const SET_MAX_LINKS_PER_PRIMARY: &str = "set_max_links_per_primary";
+    pub const GET_PRIMARY_ACCOUNT: &str = "get_primary_account";
+    pub const GET_LINKED_ACCOUNTS: &str = "get_linked_accounts";
+    pub const SET_PRIVACY_MODE: &str = "set_privacy_mode";
+    pub const SET_THIRD_PARTY_ACCESS: &str = "set_third_party_access";
+    pub const HAS_THIRD_PARTY_ACCESS: &str = "has_third_party_access";
+    pub const ADD_CONSUMER: &str = "add_consumer";
+    pub const REMOVE_CONSUMER: &str = "remove_consumer";
+    pub const IS_CONSUMER: &str = "is_consumer";
+    pub const SET_RESTRICTED_MODE: &str = "set_restricted_mode";
+    pub const SET_PROVIDER_TIER: &str = "set_provider_tier";
+    pub const GET_PROVIDER_TIER: &str = "get_provider_tier";
+    pub const SET_CONSUMER_PROFILE: &str = "set_consumer_profile";
+    pub const REMOVE_CONSUMER_PROFILE: &str = "remove_consumer_profile";
+    pub const GET_CONSUMER_PROFILE: &str = "get_consumer_profile";
+    pub const SET_USAGE_METERING: &str = "set_usage_metering";
+    pub const GET_CONSUMER_USAGE_STATS: &str = "get_consumer_usage_stats";
+    pub const GET_PROVIDER_USAGE_STATS: &str = "get_provider_usage_stats";
+    pub const RESET_USAGE: &str = "reset_usage";
+    pub const ADD_ADMIN: &str = "add_admin";
+    pub const REMOVE_ADMIN: &str = "remove_admin";
+    pub const IS_ADMIN: &str = "is_admin";
+    pub const MIGRATE_STORAGE: &str = "migrate_storage";
+}
+
+/// Runtime arguments of the proxy's installer, entry points and session code.
+pub mod args {
+    /// Name of the proxy, used as prefix of the installer's named keys.
+    pub const PROXY_NAME: &str = "name";
+    pub const INITIAL_PROVIDERS: &str = "initial_providers";
+    pub const ACCOUNT: &str = "account";
+    pub const ACCOUNTS: &str = "accounts";
+    pub const PUBLIC_KEY: &str = "public_key";
+    pub const INDEX: &str = "index";
+    pub const JURISDICTIONS: &str = "jurisdictions";
+    pub const PROVIDER: &str = "provider";
+    /// Display name of a provider in its metadata.
+    pub const PROVIDER_NAME: &str = "name";
+    pub const WEBSITE: &str = "website";
+    pub const CONTACT: &str = "contact";
+    pub const DESCRIPTION: &str = "description";
+    pub const IS_PROXY: &str = "is_proxy";
+    pub const STATUS: &str = "status";
+    pub const ADAPTER: &str = "adapter";
+    pub const ADDRESSING: &str = "addressing";
+    pub const NORMALIZATION: &str = "normalization";
+    /// Reason of a ban, stored in the provider's status.
+    pub const REASON: &str = "reason";
+    pub const MAX_DEPTH: &str = "max_depth";
+    pub const PRIMARY: &str = "primary";
+    pub const SECONDARY: &str = "secondary";
+    pub const MAX_LINKS: &str = "max_links";
+    pub const ENABLED: &str = "enabled";
+    pub const CONSUMER: &str = "consumer";
+    pub const ALLOWED: &str = "allowed";
+    pub const TIER: &str = "tier";
+    /// Providers a check is restricted to, or a consumer profile trusts.
+    pub const PROVIDERS: &str = "providers";
+    pub const THRESHOLD: &str = "threshold";
+    pub const MIN_TIER: &str = "min_tier";
+    /// Account granted or revoked admin rights over the proxy.
+    pub const ADMIN: &str = "admin";
+    pub const PROXY_HOPS: &str = "proxy_hops";
+    pub const VISITED_PROXIES: &str = "visited_proxies";
+    pub const KYC_PROXY_PACKAGE_HASH: &str = "kyc_proxy_package_hash";
+    pub const NAMED_KEY: &str = "named_key";
+}
+
+/// Named keys of the proxy contract, and of the accounts that install or query it.
+pub mod named_keys {
+    /// Dictionary of the provider package hashes by decimal index.
+    pub const KYC_PROVIDER_INDEX: &str = "kyc_provider_index";
+    /// Dictionary of the `ProviderRecord`s by provider package hash.
+    pub const KYC_PROVIDER_RECORDS: &str = "kyc_provider_records";
+    /// Number of stored providers.
+    pub const KYC_PROVIDER_COUNT: &str = "kyc_provider_count";
+    /// List of the unbanned providers as `ActiveProvider`s, in the order they were stored.
+    pub const KYC_ACTIVE_PROVIDERS: &str = "kyc_active_providers";
+    /// Version of the storage schema, missing for the first one.
+    pub const SCHEMA_VERSION: &str = "schema_version";
+    /// Dictionary of the first storage schema, holding indices, status entries and `LEN`.
+    pub const KYC_PROVIDERS: &str = "kyc_providers";
+    pub const KYC_PROVIDER_JURISDICTIONS: &str = "kyc_provider_jurisdictions";
+    pub const KYC_PROVIDER_METADATA: &str = "kyc_provider_metadata";
+    pub const KYC_NESTED_PROXIES: &str = "kyc_nested_proxies";
+    /// Dictionary of the `ProviderAdapter`s by provider package hash, missing for `KycProvider`.
+    pub const KYC_PROVIDER_ADAPTERS: &str = "kyc_provider_adapters";
+    /// Dictionary of the `ProviderAddressing`s by provider hash, missing for `Package`.
+    pub const KYC_PROVIDER_ADDRESSING: &str = "kyc_provider_addressing";
+    /// Dictionary of the `AccountNormalization`s by provider hash, missing for `Verbatim`.
+    pub const KYC_PROVIDER_NORMALIZATION: &str = "kyc_provider_normalization";
+    pub const MAX_PROXY_DEPTH: &str = "max_proxy_depth";
+    /// Dictionary of the primary account each secondary account was last proposed a link by,
+    /// as `Option<AccountHash>` by secondary account hash.
+    pub const KYC_LINK_PROPOSALS: &str = "kyc_link_proposals";
+    /// Dictionary of the primary account of each linked secondary account, as `Option<AccountHash>`
+    /// by secondary account hash.
+    pub const KYC_ACCOUNT_LINKS: &str = "kyc_account_links";
+    /// Dictionary of the secondary accounts linked to each primary account, by primary account hash.
+    pub const KYC_LINKED_ACCOUNTS: &str = "kyc_linked_accounts";
+    pub const MAX_LINKS_PER_PRIMARY: &str = "max_links_per_primary";
+    /// Whether only consumers with third-party access may check other accounts than the caller's.
+    pub const PRIVACY_MODE: &str = "privacy_mode";
+    /// Dictionary of whether each consumer contract has third-party access, by package hash.
+    pub const KYC_THIRD_PARTY_ACCESS: &str = "kyc_third_party_access";
+    /// Whether only registered consumers may call the proxy's checks.
+    pub const RESTRICTED_MODE: &str = "restricted_mode";
+    /// Dictionary of whether each consumer contract is registered, by package hash.
+    pub const KYC_CONSUMER_CONTRACTS: &str = "kyc_consumer_contracts";
+    /// Dictionary of whether each consumer account is registered, by account hash.
+    pub const KYC_CONSUMER_ACCOUNTS: &str = "kyc_consumer_accounts";
+    /// Dictionary of the tier of each provider by provider hash, missing for 0.
+    pub const KYC_PROVIDER_TIERS: &str = "kyc_provider_tiers";
+    /// Dictionary of the `ConsumerProfile` of each consumer contract, as `Option<ConsumerProfile>`
+    /// by package hash.
+    pub const KYC_CONSUMER_PROFILES: &str = "kyc_consumer_profiles";
+    /// Whether the checks update the usage counters.
+    pub const USAGE_METERING: &str = "usage_metering";
+    /// Number of the current billing period, incremented by `reset_usage`.
+    pub const USAGE_PERIOD: &str = "usage_period";
+    /// Dictionary of the checks called by each consumer contract, by package hash. Like the other
+    /// usage counters, each entry is a `(u32, u64)` of the billing period and the count.
+    pub const KYC_CONTRACT_CALLS: &str = "kyc_contract_calls";
+    /// Dictionary of the checks called by each consumer account, by account hash.
+    pub const KYC_ACCOUNT_CALLS: &str = "kyc_account_calls";
+    /// Dictionary of the times each provider was asked, by provider hash.
+    pub const KYC_PROVIDER_CALLS: &str = "kyc_provider_calls";
+    /// Dictionary of the approvals each provider answered, by provider hash.
+    pub const KYC_PROVIDER_APPROVALS: &str = "kyc_provider_approvals";
+    /// Dictionary of whether each account is an admin of the proxy, by account hash.
+    pub const KYC_ADMINS: &str = "kyc_admins";
+    /// Key of the first schema's `kyc_providers` entry holding the number of stored providers.
+    pub const LEN: &str = "len";
+    /// Named key `record_kyc_verdict` stores the verdict under if none is given.
+    pub const KYC_VERDICT: &str = "kyc_verdict";
+}
+
+/// Named key of the installing account holding the proxy's package hash.
+pub fn package_hash_key(proxy_name: &str) -> String {
+    format!("{}-proxy_package_hash", proxy_name)
+}
+
+/// Named key of the installing account holding the proxy's contract hash.
+pub fn contract_key(proxy_name: &str) -> String {
+    format!("{}-proxy_contract", proxy_name)
+}
+
+/// Named key of the installing account holding the proxy package's access URef.
+pub fn access_token_key(proxy_name: &str) -> String {
+    format!("{}-proxy_access_token", proxy_name)
+}
+
+/// Named key of the installing account holding a URef to the proxy's contract hash.
+pub fn contract_hash_key(proxy_name: &str) -> String {
+    format!("{}-proxy_contract_hash", proxy_name)
+}
+
+/// Named key of the proxy contract holding a URef to its own package hash.
+pub fn contract_package_key(proxy_name: &str) -> String {
+    format!("{}-proxy_contract_package", proxy_name)
+}
+
+/// Names of the reference KYC provider contract `kyc-provider`.
+pub mod provider {
+    use alloc::{format, string::String};
+
+    /// Entry points of the installed provider contract.
+    pub mod entry_points {
+        pub const INIT: &str = "init";
+        pub const IS_KYC_PROVED: &str = super::super::entry_points::IS_KYC_PROVED;
+        pub const APPROVE: &str = "approve";
+        pub const REVOKE: &str = "revoke";
+        pub const ADD_ADMIN: &str = "add_admin";
+        pub const REMOVE_ADMIN: &str = "remove_admin";
+    }
+
+    /// Runtime arguments of the provider's installer and entry points.
+    pub mod args {
+        /// Name of the provider, used as prefix of the installer's named keys.
+        pub const PROVIDER_NAME: &str = "name";
+        pub const ADMINS: &str = "admins";
+        pub const ADMIN: &str = "admin";
+        pub const ACCOUNT: &str = super::super::args::ACCOUNT;
+        pub const INDEX: &str = super::super::args::INDEX;
+        /// Block time at which an approval stops proving the account.
+        pub const EXPIRES_AT: &str = "expires_at";
+    }
+
+    /// Named keys of the provider contract.
+    pub mod named_keys {
+        pub const KYC_APPROVALS: &str = "kyc_approvals";
+        pub const KYC_ADMINS: &str = "kyc_admins";
+    }
+
+    /// Named key of the installing account holding a URef to the provider's package hash.
+    pub fn package_hash_key(provider_name: &str) -> String {
+        format!("{}-provider_package_hash", provider_name)
+    }
+
+    /// Named key of the installing account holding a URef to the provider's contract hash.
+    pub fn contract_hash_key(provider_name: &str) -> String {
+        format!("{}-provider_contract_hash", provider_name)
+    }
+
+    /// Named key of the installing account holding the provider package's access URef.
+    pub fn access_token_key(provider_name: &str) -> String {
+        format!("{}-provider_access_token", provider_name)
+    }
+}
+
+/// Names of the mock KYC provider contract `mock_provider` used by the tests.
+pub mod mock_provider {
+    /// Entry points of the installed mock provider, besides `is_kyc_proved` and the CEP-78 `balance_of`.
+    pub mod entry_points {
+        pub const INIT: &str = "init";
+        pub const SET_ANSWER: &str = "set_answer";
+        pub const SET_DEFAULT_ANSWER: &str = "set_default_answer";
+    }
+
+    /// Runtime arguments of the mock provider's entry points.
+    pub mod args {
+        pub const ACCOUNT: &str = super::super::args::ACCOUNT;
+        /// `MockAnswer` code to answer with.
+        pub const ANSWER: &str = "answer";
+    }
+
+    /// Named keys of the mock provider contract.
+    pub mod named_keys {
+        pub const MOCK_ANSWERS: &str = "mock_answers";
+        pub const MOCK_DEFAULT_ANSWER: &str = "mock_default_answer";
+    }
+}
+
+/// Names of the consumer contract `kyc_consumer` used by the tests, which checks accounts through the proxy.
+pub mod consumer {
+    use alloc::{format, string::String};
+
+    /// Entry points of the installed consumer contract.
+    pub mod entry_points {
+        pub const CHECK: &str = "check";
+    }
+
+    /// Runtime arguments of the consumer's installer and entry points.
+    pub mod args {
+        /// Name of the consumer, used as prefix of the installer's named keys.
+        pub const CONSUMER_NAME: &str = "name";
+        pub const KYC_PROXY_PACKAGE_HASH: &str = super::super::args::KYC_PROXY_PACKAGE_HASH;
+        pub const ACCOUNT: &str = super::super::args::ACCOUNT;
+        /// Answer the proxy is expected to give.
+        pub const RESULT: &str = "result";
+    }
+
+    /// Named key of the installing account holding a URef to the consumer's package hash.
+    pub fn package_hash_key(consumer_name: &str) -> String {
+        format!("{}-consumer_package_hash", consumer_name)
+    }
+
+    /// Named key of the installing account holding a URef to the consumer's contract hash.
+    pub fn contract_hash_key(consumer_name: &str) -> String {
+        format!("{}-consumer_contract_hash", consumer_name)
+    }
+}
+
+/// Names of the KYC token contracts the proxy can ask through a `ProviderAdapter`.
+pub mod token {
+    pub mod entry_points {
+        pub const BALANCE_OF: &str = "balance_of";
+    }
+
+    pub mod args {
+        /// Account argument of the CEP-47 `balance_of`.
+        pub const CEP47_OWNER: &str = "owner";
+        /// Account argument of the CEP-78 `balance_of`.
+        pub const CEP78_TOKEN_OWNER: &str = "token_owner";
+    }
+}
@@ -0,0 +1,77 @@
+//! Filtering of the account identifiers a check is made with by the form a provider stores.
+
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, PublicKey,
+};
+
+use crate::error::Error;
+
+const VERBATIM_TAG: u8 = 0;
+const ACCOUNT_TAG: u8 = 1;
+
+/// Which account identifiers a provider is asked with. Keys are passed on as given, never converted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountNormalization {
+    /// Pass the account `Key` as given.
+    Verbatim,
+    /// Pass only `Key::Account`s, skipping the provider for any other `Key` variant.
+    AccountOnly,
+}
+
+impl AccountNormalization {
+    /// The `Key` a provider with this normalization is asked with for `account`, which is `account`
+    /// itself unless the provider is skipped for it.
+    pub fn normalize(&self, account: Key) -> Result<Key, Error> {
+        match (self, account) {
+            (AccountNormalization::Verbatim, _)
+            | (AccountNormalization::AccountOnly, Key::Account(_)) => Ok(account),
+            (AccountNormalization::AccountOnly, _) => Err(Error::UnsupportedAccountKey),
+        }
+    }
+}
+
+impl Default for AccountNormalization {
+    fn default() -> Self {
+        AccountNormalization::Verbatim
+    }
+}
+
+/// The `Key::Account` of the account whose main key is `public_key`.
+pub fn public_key_account(public_key: &PublicKey) -> Key {
+    Key::Account(public_key.to_account_hash())
+}
+
+impl CLTyped for AccountNormalization {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for AccountNormalization {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let tag = match self {
+            AccountNormalization::Verbatim => VERBATIM_TAG,
+            AccountNormalization::AccountOnly => ACCOUNT_TAG,
+        };
+        tag.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        1
+    }
+}
+
+impl FromBytes for AccountNormalization {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        let normalization = match tag {
+            VERBATIM_TAG => AccountNormalization::Verbatim,
+            ACCOUNT_TAG => AccountNormalization::AccountOnly,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((normalization, bytes))
+    }
+}
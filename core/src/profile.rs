@@ -0,0 +1,90 @@
+//! Check policies of individual consumer contracts.
+
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, ContractPackageHash,
+};
+
+use crate::{caller::ActiveProvider, error::Error};
+
+/// How the checks of one consumer contract are answered, in place of the global configuration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsumerProfile {
+    /// Providers the consumer trusts, or `None` for all of them.
+    pub providers: Option<Vec<ContractPackageHash>>,
+    /// Number of providers that have to approve an account.
+    pub threshold: u8,
+    /// Lowest tier of the providers asked.
+    pub min_tier: u8,
+}
+
+impl ConsumerProfile {
+    /// Check that the profile can ever approve an account: at least one provider has to approve.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.threshold == 0 {
+            return Err(Error::InvalidConsumerProfile);
+        }
+        Ok(())
+    }
+
+    /// Whether the consumer has `provider` asked.
+    pub fn trusts(&self, provider: &ActiveProvider) -> bool {
+        provider.tier >= self.min_tier
+            && self
+                .providers
+                .as_ref()
+                .map_or(true, |providers| providers.contains(&provider.package_hash))
+    }
+}
+
+/// The global configuration: every provider is asked, and the first approval proves the account.
+impl Default for ConsumerProfile {
+    fn default() -> Self {
+        ConsumerProfile {
+            providers: None,
+            threshold: 1,
+            min_tier: 0,
+        }
+    }
+}
+
+/// The profile is serialized like `(providers, threshold, min_tier)` would be, so it is typed as that tuple.
+impl CLTyped for ConsumerProfile {
+    fn cl_type() -> CLType {
+        <(Option<Vec<ContractPackageHash>>, u8, u8)>::cl_type()
+    }
+}
+
+impl ToBytes for ConsumerProfile {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.providers.to_bytes()?);
+        result.append(&mut self.threshold.to_bytes()?);
+        result.append(&mut self.min_tier.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.providers.serialized_length()
+            + self.threshold.serialized_length()
+            + self.min_tier.serialized_length()
+    }
+}
+
+impl FromBytes for ConsumerProfile {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (providers, bytes) = Option::<Vec<ContractPackageHash>>::from_bytes(bytes)?;
+        let (threshold, bytes) = u8::from_bytes(bytes)?;
+        let (min_tier, bytes) = u8::from_bytes(bytes)?;
+        Ok((
+            ConsumerProfile {
+                providers,
+                threshold,
+                min_tier,
+            },
+            bytes,
+        ))
+    }
+}
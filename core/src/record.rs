@@ -0,0 +1,131 @@
+//! Status records of the registered providers.
+
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+const ACTIVE_TAG: u8 = 0;
+const SUSPENDED_TAG: u8 = 1;
+const DEPRECATED_TAG: u8 = 2;
+const BANNED_TAG: u8 = 3;
+
+/// Whether and how a registered provider is asked in checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProviderStatus {
+    /// Asked in checks.
+    Active,
+    /// Not asked in checks until the block time `until`, in milliseconds, after which it is active again.
+    Suspended { until: u64, reason: String },
+    /// Still asked in checks, but flagged for consumers to migrate away from.
+    Deprecated,
+    /// Not asked in checks until its status is set again.
+    Banned { reason: String },
+}
+
+impl ProviderStatus {
+    pub fn is_banned(&self) -> bool {
+        matches!(self, ProviderStatus::Banned { .. })
+    }
+
+    /// Block time the provider is suspended until, if it is suspended.
+    pub fn suspended_until(&self) -> Option<u64> {
+        match self {
+            ProviderStatus::Suspended { until, .. } => Some(*until),
+            _ => None,
+        }
+    }
+}
+
+impl CLTyped for ProviderStatus {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ProviderStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            ProviderStatus::Active => result.push(ACTIVE_TAG),
+            ProviderStatus::Suspended { until, reason } => {
+                result.push(SUSPENDED_TAG);
+                result.append(&mut until.to_bytes()?);
+                result.append(&mut reason.to_bytes()?);
+            }
+            ProviderStatus::Deprecated => result.push(DEPRECATED_TAG),
+            ProviderStatus::Banned { reason } => {
+                result.push(BANNED_TAG);
+                result.append(&mut reason.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            ProviderStatus::Active | ProviderStatus::Deprecated => 0,
+            ProviderStatus::Suspended { until, reason } => {
+                until.serialized_length() + reason.serialized_length()
+            }
+            ProviderStatus::Banned { reason } => reason.serialized_length(),
+        }
+    }
+}
+
+impl FromBytes for ProviderStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        match tag {
+            ACTIVE_TAG => Ok((ProviderStatus::Active, bytes)),
+            SUSPENDED_TAG => {
+                let (until, bytes) = u64::from_bytes(bytes)?;
+                let (reason, bytes) = String::from_bytes(bytes)?;
+                Ok((ProviderStatus::Suspended { until, reason }, bytes))
+            }
+            DEPRECATED_TAG => Ok((ProviderStatus::Deprecated, bytes)),
+            BANNED_TAG => {
+                let (reason, bytes) = String::from_bytes(bytes)?;
+                Ok((ProviderStatus::Banned { reason }, bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+/// Entry of a provider in the "kyc_provider_records" dictionary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProviderRecord {
+    /// Position of the provider in the "kyc_provider_index" dictionary.
+    pub index: u64,
+    pub status: ProviderStatus,
+}
+
+impl CLTyped for ProviderRecord {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ProviderRecord {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.index.to_bytes()?);
+        result.append(&mut self.status.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.index.serialized_length() + self.status.serialized_length()
+    }
+}
+
+impl FromBytes for ProviderRecord {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (index, bytes) = u64::from_bytes(bytes)?;
+        let (status, bytes) = ProviderStatus::from_bytes(bytes)?;
+        Ok((ProviderRecord { index, status }, bytes))
+    }
+}
@@ -0,0 +1,1329 @@
+//! The registry of KYC providers and the checks answered with it.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_types::{account::AccountHash, ContractHash, ContractPackageHash, Key, U256};
+
+use crate::{
+    adapter::ProviderAdapter,
+    addressing::{ProviderAddressing, ProviderHash},
+    caller::{ActiveProvider, ProviderCaller},
+    chain::ProxyChain,
+    consumer::Consumer,
+    error::Error,
+    metadata::{ProviderMetadata, ProviderMetadataUpdate},
+    names::named_keys,
+    normalization::AccountNormalization,
+    profile::ConsumerProfile,
+    record::{ProviderRecord, ProviderStatus},
+    store::ProviderStore,
+    usage::ProviderUsage,
+};
+
+/// Maximum number of nested proxies a check may pass through, until changed with `set_max_proxy_depth`.
+pub const DEFAULT_MAX_PROXY_DEPTH: u8 = 4;
+
+/// Maximum number of secondary accounts linked to one primary account, until changed with `set_max_links_per_primary`.
+pub const DEFAULT_MAX_LINKS_PER_PRIMARY: u32 = 5;
+
+/// Version of the storage schema written by `init` and `migrate`.
+pub const CURRENT_SCHEMA_VERSION: u8 = 2;
+
+/// The providers registered with the proxy `own_package_hash`, kept in a `ProviderStore`.
+///
+/// The "kyc_provider_index" dictionary holds the package hash of each provider under its decimal
+/// index, the "kyc_provider_records" dictionary holds its `ProviderRecord` under the package hash
+/// string, and "kyc_provider_count" holds the number of providers. The unbanned providers are
+/// kept again in the "kyc_active_providers" list, so a check reads them at once.
+/// Providers registered by contract hash are kept under that hash in place of a package hash,
+/// with their addressing stored in the "kyc_provider_addressing" dictionary.
+/// Checks skip the providers whose `AccountNormalization` does not accept the account, and fail
+/// with `Error::UnsupportedAccountKey` if it is accepted by none of the providers they would ask.
+/// A secondary account linked to a primary account with `propose_link` and `accept_link` is
+/// KYC proved if no provider approves it but one approves its primary account.
+/// In privacy mode, `authorize_check` only lets consumers with third-party access check other
+/// accounts than the caller's own.
+/// In restricted mode, `authorize_check` only lets consumers registered with `add_consumer` check
+/// any account, their own included.
+/// A registry opened `with_consumer` a consumer contract that has a `ConsumerProfile` only asks the
+/// providers of the profile with at least its minimum tier, and needs as many approvals as its threshold.
+/// With usage metering on, the checks count the calls of their consumer and the calls and approvals of
+/// each provider they ask, for the billing period started by the last `reset_usage`.
+/// Suspensions are compared with the block time given to `with_blocktime`, 0 unless set.
+/// The accounts of the "kyc_admins" dictionary manage the registry; the contract checks the caller
+/// of its admin entry points with `require_admin`.
+pub struct Registry<S: ProviderStore> {
+    store: S,
+    own_package_hash: ContractPackageHash,
+    own_contract_hash: Option<ContractHash>,
+    len: u64,
+    blocktime: u64,
+    profile: ConsumerProfile,
+    consumer: Option<Consumer>,
+    usage_metering: bool,
+}
+
+impl<S: ProviderStore> Registry<S> {
+    /// Create the "kyc_provider_index" and "kyc_provider_records" dictionaries and store `initial_providers` in them,
+    /// while setting their status as validated.
+    /// The "kyc_provider_jurisdictions", "kyc_provider_metadata", "kyc_nested_proxies", "kyc_provider_adapters",
+    /// "kyc_provider_addressing" and "kyc_provider_normalization" dictionaries are created next to them,
+    /// with the "kyc_link_proposals", "kyc_account_links" and "kyc_linked_accounts" dictionaries of linked accounts,
+    /// the "kyc_third_party_access", "kyc_consumer_contracts" and "kyc_consumer_accounts" dictionaries
+    /// the "kyc_provider_tiers" and "kyc_consumer_profiles" dictionaries and the dictionaries of usage counters,
+    /// as well as the "max_proxy_depth" value. Each initial provider gets a copy of `registered` as metadata record.
+    /// The "kyc_admins" dictionary is created with `admin` as the first admin.
+    pub fn init(
+        mut store: S,
+        own_package_hash: ContractPackageHash,
+        admin: AccountHash,
+        initial_providers: Vec<ContractPackageHash>,
+        registered: ProviderMetadata,
+    ) -> Result<Self, Error> {
+        new_dictionaries(&mut store, admin);
+        store.write(named_keys::KYC_PROVIDER_COUNT, 0u64);
+        store.write(
+            named_keys::KYC_ACTIVE_PROVIDERS,
+            Vec::<ActiveProvider>::new(),
+        );
+        store.write(named_keys::SCHEMA_VERSION, CURRENT_SCHEMA_VERSION);
+        let mut registry = Registry {
+            store,
+            own_package_hash,
+            own_contract_hash: None,
+            len: 0,
+            blocktime: 0,
+            profile: ConsumerProfile::default(),
+            consumer: None,
+            usage_metering: false,
+        };
+        for provider_package_hash in initial_providers {
+            registry.add_kyc_provider(
+                provider_package_hash,
+                registered.clone(),
+                ProviderMetadataUpdate::default(),
+            )?;
+        }
+        Ok(registry)
+    }
+
+    /// Open the registry created by `init`, reading the number of stored providers.
+    /// Storage of an older schema has to be converted with `migrate` first, and storage without
+    /// the number of providers fails with `Error::UninitializedRegistry`.
+    pub fn open(store: S, own_package_hash: ContractPackageHash) -> Result<Self, Error> {
+        match store.read::<u8>(named_keys::SCHEMA_VERSION) {
+            Some(CURRENT_SCHEMA_VERSION) => {}
+            Some(version) if version < CURRENT_SCHEMA_VERSION => return Err(Error::OutdatedSchema),
+            Some(_) => return Err(Error::UnsupportedSchema),
+            None => return Err(Error::OutdatedSchema),
+        }
+        let len: u64 = store
+            .read(named_keys::KYC_PROVIDER_COUNT)
+            .ok_or(Error::UninitializedRegistry)?;
+        let usage_metering = store.read(named_keys::USAGE_METERING).unwrap_or_default();
+        Ok(Registry {
+            store,
+            own_package_hash,
+            own_contract_hash: None,
+            len,
+            blocktime: 0,
+            profile: ConsumerProfile::default(),
+            consumer: None,
+            usage_metering,
+        })
+    }
+
+    /// Convert storage of the first, unversioned schema in place and open the registry.
+    ///
+    /// The first schema only had the "kyc_providers" dictionary. Its providers are copied in index
+    /// order into the "kyc_provider_index" and "kyc_provider_records" dictionaries, with their status,
+    /// and each gets a copy of `registered` as metadata record. The other dictionaries created by
+    /// `init` are created empty, with `admin` as the first admin, the "kyc_active_providers" list is
+    /// built from the records and the "kyc_providers" named key is removed.
+    /// Storage that is already current is opened unchanged, and `admin` and `registered` are ignored.
+    pub fn migrate(
+        mut store: S,
+        own_package_hash: ContractPackageHash,
+        admin: AccountHash,
+        registered: ProviderMetadata,
+    ) -> Result<Self, Error> {
+        match store.read::<u8>(named_keys::SCHEMA_VERSION) {
+            Some(CURRENT_SCHEMA_VERSION) => return Self::open(store, own_package_hash),
+            Some(_) => return Err(Error::UnsupportedSchema),
+            None if store.contains(named_keys::KYC_PROVIDERS) => {}
+            None => return Err(Error::UnsupportedSchema),
+        }
+        new_dictionaries(&mut store, admin);
+        migrate_v1_providers(&mut store, registered);
+        store.write(named_keys::SCHEMA_VERSION, CURRENT_SCHEMA_VERSION);
+        let mut registry = Self::open(store, own_package_hash)?;
+        registry.rebuild_active_providers();
+        Ok(registry)
+    }
+
+    /// Set the block time, in milliseconds, that suspensions end at.
+    pub fn with_blocktime(mut self, blocktime: u64) -> Self {
+        self.blocktime = blocktime;
+        self
+    }
+
+    /// Set the hash of the proxy's contract, which `add_kyc_provider` rejects like its package hash.
+    pub fn with_own_contract_hash(mut self, own_contract_hash: ContractHash) -> Self {
+        self.own_contract_hash = Some(own_contract_hash);
+        self
+    }
+
+    /// Answer the checks with the profile of `consumer` if it is a contract that has one, and meter
+    /// them as its calls.
+    pub fn with_consumer(mut self, consumer: Consumer) -> Self {
+        self.consumer = Some(consumer);
+        if let Consumer::Contract(consumer_package_hash) = consumer {
+            self.profile = self
+                .get_consumer_profile(consumer_package_hash)
+                .unwrap_or_default();
+        }
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// Number of stored providers, banned ones included.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Store a new KYC provider and set their status as validated.
+    /// Its metadata record `registered` is stored alongside, with the given fields applied.
+    /// The proxy itself can not be registered as a provider, by its package hash or by the contract
+    /// hash given to `with_own_contract_hash`.
+    pub fn add_kyc_provider(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        mut registered: ProviderMetadata,
+        metadata_update: ProviderMetadataUpdate,
+    ) -> Result<(), Error> {
+        let own_contract_hash = self.own_contract_hash.map(ProviderHash::Contract);
+        if provider_package_hash == self.own_package_hash
+            || own_contract_hash
+                == Some(ProviderAddressing::Contract.provider_hash(provider_package_hash))
+        {
+            return Err(Error::SelfReference);
+        }
+        let str_provider = provider_package_hash.to_string();
+        if self.record(&str_provider).is_none() {
+            self.store.dictionary_put(
+                named_keys::KYC_PROVIDER_INDEX,
+                &self.len.to_string(),
+                provider_package_hash,
+            );
+            self.store.dictionary_put(
+                named_keys::KYC_PROVIDER_RECORDS,
+                &str_provider,
+                ProviderRecord {
+                    index: self.len,
+                    status: ProviderStatus::Active,
+                },
+            );
+            self.len += 1;
+            self.store.write(named_keys::KYC_PROVIDER_COUNT, self.len);
+            let mut active = self.stored_active_providers();
+            active.push(self.list_entry(provider_package_hash, None));
+            self.store.write(named_keys::KYC_ACTIVE_PROVIDERS, active);
+            metadata_update.apply(&mut registered);
+            self.store
+                .dictionary_put(named_keys::KYC_PROVIDER_METADATA, &str_provider, registered);
+        }
+        Ok(())
+    }
+
+    /// Mark a stored provider as a nested proxy, or unmark it.
+    pub fn set_provider_is_proxy(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        is_proxy: bool,
+    ) {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            self.store
+                .dictionary_put(named_keys::KYC_NESTED_PROXIES, &str_provider, is_proxy);
+            self.update_list_entry(provider_package_hash, |provider| {
+                provider.is_proxy = is_proxy
+            });
+        }
+    }
+
+    /// Set the adapter the proxy asks a stored provider through.
+    pub fn set_provider_adapter(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        adapter: ProviderAdapter,
+    ) -> Result<(), Error> {
+        adapter.validate()?;
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            self.store.dictionary_put(
+                named_keys::KYC_PROVIDER_ADAPTERS,
+                &str_provider,
+                adapter.clone(),
+            );
+            self.update_list_entry(provider_package_hash, |provider| provider.adapter = adapter);
+        }
+        Ok(())
+    }
+
+    /// Return the adapter of a provider, if it is stored.
+    pub fn get_provider_adapter(
+        &self,
+        provider_package_hash: ContractPackageHash,
+    ) -> Option<ProviderAdapter> {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            Some(self.adapter(&str_provider))
+        } else {
+            None
+        }
+    }
+
+    /// Set whether a stored provider is called as a contract package or at a fixed contract hash.
+    pub fn set_provider_addressing(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        addressing: ProviderAddressing,
+    ) {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            self.store.dictionary_put(
+                named_keys::KYC_PROVIDER_ADDRESSING,
+                &str_provider,
+                addressing,
+            );
+            self.update_list_entry(provider_package_hash, |provider| {
+                provider.hash = addressing.provider_hash(provider.package_hash)
+            });
+        }
+    }
+
+    /// Return the addressing of a provider, if it is stored.
+    pub fn get_provider_addressing(
+        &self,
+        provider_package_hash: ContractPackageHash,
+    ) -> Option<ProviderAddressing> {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            Some(self.addressing(&str_provider))
+        } else {
+            None
+        }
+    }
+
+    /// Set which account identifiers a stored provider is asked with.
+    pub fn set_provider_normalization(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        normalization: AccountNormalization,
+    ) {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            self.store.dictionary_put(
+                named_keys::KYC_PROVIDER_NORMALIZATION,
+                &str_provider,
+                normalization,
+            );
+            self.update_list_entry(provider_package_hash, |provider| {
+                provider.normalization = normalization
+            });
+        }
+    }
+
+    /// Return the account normalization of a provider, if it is stored.
+    pub fn get_provider_normalization(
+        &self,
+        provider_package_hash: ContractPackageHash,
+    ) -> Option<AccountNormalization> {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            Some(self.normalization(&str_provider))
+        } else {
+            None
+        }
+    }
+
+    /// Set the tier of a stored provider.
+    pub fn set_provider_tier(&mut self, provider_package_hash: ContractPackageHash, tier: u8) {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            self.store
+                .dictionary_put(named_keys::KYC_PROVIDER_TIERS, &str_provider, tier);
+            self.update_list_entry(provider_package_hash, |provider| provider.tier = tier);
+        }
+    }
+
+    /// Return the tier of a provider, if it is stored.
+    pub fn get_provider_tier(&self, provider_package_hash: ContractPackageHash) -> Option<u8> {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            Some(self.tier(&str_provider))
+        } else {
+            None
+        }
+    }
+
+    /// Check whether a provider is marked as a nested proxy.
+    pub fn is_proxy(&self, provider_package_hash: ContractPackageHash) -> bool {
+        self.store
+            .dictionary_get::<bool>(
+                named_keys::KYC_NESTED_PROXIES,
+                &provider_package_hash.to_string(),
+            )
+            .unwrap_or_default()
+    }
+
+    /// Set the maximum number of nested proxies a check through this proxy may pass through.
+    pub fn set_max_proxy_depth(&mut self, max_depth: u8) {
+        self.store.write(named_keys::MAX_PROXY_DEPTH, max_depth);
+    }
+
+    pub fn max_proxy_depth(&self) -> u8 {
+        self.store
+            .read(named_keys::MAX_PROXY_DEPTH)
+            .unwrap_or(DEFAULT_MAX_PROXY_DEPTH)
+    }
+
+    /// Update the metadata record of a stored provider.
+    pub fn update_provider_metadata(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        metadata_update: ProviderMetadataUpdate,
+    ) {
+        let str_provider = provider_package_hash.to_string();
+        if let Some(mut metadata) = self
+            .store
+            .dictionary_get::<ProviderMetadata>(named_keys::KYC_PROVIDER_METADATA, &str_provider)
+        {
+            metadata_update.apply(&mut metadata);
+            self.store
+                .dictionary_put(named_keys::KYC_PROVIDER_METADATA, &str_provider, metadata);
+        }
+    }
+
+    /// Return the metadata record of a provider, if it is stored.
+    pub fn get_provider_info(
+        &self,
+        provider_package_hash: ContractPackageHash,
+    ) -> Option<ProviderMetadata> {
+        self.store.dictionary_get(
+            named_keys::KYC_PROVIDER_METADATA,
+            &provider_package_hash.to_string(),
+        )
+    }
+
+    /// Set a stored provider as invalid, with the reason given.
+    pub fn ban_provider(&mut self, provider_package_hash: ContractPackageHash, reason: String) {
+        self.set_provider_status(provider_package_hash, ProviderStatus::Banned { reason })
+    }
+
+    /// Set a banned provider as validated, leaving providers with any other status alone.
+    pub fn unban_provider(&mut self, provider_package_hash: ContractPackageHash) {
+        let is_banned = self
+            .get_provider_status(provider_package_hash)
+            .map_or(false, |status| status.is_banned());
+        if is_banned {
+            self.set_provider_status(provider_package_hash, ProviderStatus::Active)
+        }
+    }
+
+    /// Set the status of a stored provider, leaving unknown providers alone.
+    /// Banned providers leave the "kyc_active_providers" list, and return to their place in it
+    /// when they get any other status.
+    pub fn set_provider_status(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        status: ProviderStatus,
+    ) {
+        let str_provider = provider_package_hash.to_string();
+        let mut record = match self.record(&str_provider) {
+            Some(record) => record,
+            None => return,
+        };
+        if record.status == status {
+            return;
+        }
+        let index = record.index;
+        let suspended_until = status.suspended_until();
+        let banned = status.is_banned();
+        record.status = status;
+        self.store
+            .dictionary_put(named_keys::KYC_PROVIDER_RECORDS, &str_provider, record);
+        let mut providers = self.stored_active_providers();
+        let listed = providers
+            .iter()
+            .position(|provider| provider.package_hash == provider_package_hash);
+        match (listed, banned) {
+            (Some(position), true) => {
+                providers.remove(position);
+            }
+            (Some(position), false) => providers[position].suspended_until = suspended_until,
+            (None, false) => {
+                // keep the list in the order the providers were stored
+                let position = providers.partition_point(|provider| {
+                    self.record(&provider.package_hash.to_string())
+                        .map_or(false, |other| other.index < index)
+                });
+                providers.insert(
+                    position,
+                    self.list_entry(provider_package_hash, suspended_until),
+                );
+            }
+            (None, true) => return,
+        }
+        self.store
+            .write(named_keys::KYC_ACTIVE_PROVIDERS, providers);
+    }
+
+    /// Return the status of a provider, if it is stored.
+    pub fn get_provider_status(
+        &self,
+        provider_package_hash: ContractPackageHash,
+    ) -> Option<ProviderStatus> {
+        self.record(&provider_package_hash.to_string())
+            .map(|record| record.status)
+    }
+
+    /// Return the record of a provider, if it is stored.
+    pub fn get_provider_record(
+        &self,
+        provider_package_hash: ContractPackageHash,
+    ) -> Option<ProviderRecord> {
+        self.record(&provider_package_hash.to_string())
+    }
+
+    /// Store the jurisdiction codes of a stored provider, normalized to upper case.
+    pub fn set_provider_jurisdictions(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        jurisdictions: Vec<String>,
+    ) {
+        let str_provider = provider_package_hash.to_string();
+        if self.is_stored(&str_provider) {
+            let jurisdictions: Vec<String> = jurisdictions
+                .iter()
+                .map(|jurisdiction| jurisdiction.to_ascii_uppercase())
+                .collect();
+            self.store.dictionary_put(
+                named_keys::KYC_PROVIDER_JURISDICTIONS,
+                &str_provider,
+                jurisdictions,
+            );
+        }
+    }
+
+    /// Check whether a provider is certified for at least one of the given jurisdictions.
+    /// Providers without stored jurisdictions cover none.
+    pub fn covers_any(
+        &self,
+        provider_package_hash: ContractPackageHash,
+        jurisdictions: &[String],
+    ) -> bool {
+        self.store
+            .dictionary_get::<Vec<String>>(
+                named_keys::KYC_PROVIDER_JURISDICTIONS,
+                &provider_package_hash.to_string(),
+            )
+            .unwrap_or_default()
+            .iter()
+            .any(|covered| {
+                jurisdictions
+                    .iter()
+                    .any(|requested| requested.eq_ignore_ascii_case(covered))
+            })
+    }
+
+    /// Propose to link `secondary` to `primary`, which has to be KYC proved, for `secondary` to accept.
+    /// A later proposal to the same secondary account replaces this one.
+    pub fn propose_link<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        primary: AccountHash,
+        secondary: AccountHash,
+    ) -> Result<(), Error> {
+        self.check_link(primary, secondary)?;
+        let chain = ProxyChain::default();
+        let providers = self.active_providers(&chain);
+        if !self.is_kyc_proved_by(caller, &providers, Key::Account(primary), None, &chain)? {
+            return Err(Error::PrimaryNotKycProved);
+        }
+        self.store.dictionary_put(
+            named_keys::KYC_LINK_PROPOSALS,
+            &secondary.to_string(),
+            Some(primary),
+        );
+        Ok(())
+    }
+
+    /// Link `secondary` to `primary`, which proposed it with `propose_link`.
+    pub fn accept_link(
+        &mut self,
+        secondary: AccountHash,
+        primary: AccountHash,
+    ) -> Result<(), Error> {
+        let str_secondary = secondary.to_string();
+        let proposed = self
+            .store
+            .dictionary_get::<Option<AccountHash>>(named_keys::KYC_LINK_PROPOSALS, &str_secondary)
+            .flatten();
+        if proposed != Some(primary) {
+            return Err(Error::InvalidLink);
+        }
+        self.check_link(primary, secondary)?;
+        self.store.dictionary_put(
+            named_keys::KYC_LINK_PROPOSALS,
+            &str_secondary,
+            None::<AccountHash>,
+        );
+        self.store
+            .dictionary_put(named_keys::KYC_ACCOUNT_LINKS, &str_secondary, Some(primary));
+        let mut linked = self.get_linked_accounts(primary);
+        linked.push(secondary);
+        self.store.dictionary_put(
+            named_keys::KYC_LINKED_ACCOUNTS,
+            &primary.to_string(),
+            linked,
+        );
+        Ok(())
+    }
+
+    /// Remove the link between `account` and `other`, whichever of them is the primary account.
+    pub fn unlink(&mut self, account: AccountHash, other: AccountHash) -> Result<(), Error> {
+        let (primary, secondary) = if self.get_primary_account(account) == Some(other) {
+            (other, account)
+        } else if self.get_primary_account(other) == Some(account) {
+            (account, other)
+        } else {
+            return Err(Error::InvalidLink);
+        };
+        self.store.dictionary_put(
+            named_keys::KYC_ACCOUNT_LINKS,
+            &secondary.to_string(),
+            None::<AccountHash>,
+        );
+        let mut linked = self.get_linked_accounts(primary);
+        linked.retain(|linked_account| *linked_account != secondary);
+        self.store.dictionary_put(
+            named_keys::KYC_LINKED_ACCOUNTS,
+            &primary.to_string(),
+            linked,
+        );
+        Ok(())
+    }
+
+    /// Return the primary account `secondary` is linked to, if any.
+    pub fn get_primary_account(&self, secondary: AccountHash) -> Option<AccountHash> {
+        self.store
+            .dictionary_get::<Option<AccountHash>>(
+                named_keys::KYC_ACCOUNT_LINKS,
+                &secondary.to_string(),
+            )
+            .flatten()
+    }
+
+    /// Return the secondary accounts linked to `primary`, in the order they were linked.
+    pub fn get_linked_accounts(&self, primary: AccountHash) -> Vec<AccountHash> {
+        self.store
+            .dictionary_get(named_keys::KYC_LINKED_ACCOUNTS, &primary.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Set the maximum number of secondary accounts linked to one primary account.
+    /// Existing links above a lowered maximum are kept.
+    pub fn set_max_links_per_primary(&mut self, max_links: u32) {
+        self.store
+            .write(named_keys::MAX_LINKS_PER_PRIMARY, max_links);
+    }
+
+    pub fn max_links_per_primary(&self) -> u32 {
+        self.store
+            .read(named_keys::MAX_LINKS_PER_PRIMARY)
+            .unwrap_or(DEFAULT_MAX_LINKS_PER_PRIMARY)
+    }
+
+    /// Turn privacy mode on or off.
+    pub fn set_privacy_mode(&mut self, enabled: bool) {
+        self.store.write(named_keys::PRIVACY_MODE, enabled);
+    }
+
+    pub fn privacy_mode(&self) -> bool {
+        self.store
+            .read(named_keys::PRIVACY_MODE)
+            .unwrap_or_default()
+    }
+
+    /// Allow a consumer contract to check other accounts than the caller's in privacy mode, or disallow it.
+    pub fn set_third_party_access(
+        &mut self,
+        consumer_package_hash: ContractPackageHash,
+        allowed: bool,
+    ) {
+        self.store.dictionary_put(
+            named_keys::KYC_THIRD_PARTY_ACCESS,
+            &consumer_package_hash.to_string(),
+            allowed,
+        );
+    }
+
+    pub fn has_third_party_access(&self, consumer_package_hash: ContractPackageHash) -> bool {
+        self.store
+            .dictionary_get::<bool>(
+                named_keys::KYC_THIRD_PARTY_ACCESS,
+                &consumer_package_hash.to_string(),
+            )
+            .unwrap_or_default()
+    }
+
+    /// Register a consumer, which may call the checks in restricted mode.
+    pub fn add_consumer(&mut self, consumer: Consumer) {
+        let (dictionary, item_key) = consumer_entry(
+            consumer,
+            named_keys::KYC_CONSUMER_CONTRACTS,
+            named_keys::KYC_CONSUMER_ACCOUNTS,
+        );
+        self.store.dictionary_put(dictionary, &item_key, true);
+    }
+
+    /// Unregister a consumer.
+    pub fn remove_consumer(&mut self, consumer: Consumer) {
+        let (dictionary, item_key) = consumer_entry(
+            consumer,
+            named_keys::KYC_CONSUMER_CONTRACTS,
+            named_keys::KYC_CONSUMER_ACCOUNTS,
+        );
+        self.store.dictionary_put(dictionary, &item_key, false);
+    }
+
+    pub fn is_consumer(&self, consumer: Consumer) -> bool {
+        let (dictionary, item_key) = consumer_entry(
+            consumer,
+            named_keys::KYC_CONSUMER_CONTRACTS,
+            named_keys::KYC_CONSUMER_ACCOUNTS,
+        );
+        self.store
+            .dictionary_get::<bool>(dictionary, &item_key)
+            .unwrap_or_default()
+    }
+
+    /// Turn restricted mode on or off.
+    pub fn set_restricted_mode(&mut self, enabled: bool) {
+        self.store.write(named_keys::RESTRICTED_MODE, enabled);
+    }
+
+    pub fn restricted_mode(&self) -> bool {
+        self.store
+            .read(named_keys::RESTRICTED_MODE)
+            .unwrap_or_default()
+    }
+
+    /// Set the profile the checks of a consumer contract are answered with.
+    pub fn set_consumer_profile(
+        &mut self,
+        consumer_package_hash: ContractPackageHash,
+        profile: ConsumerProfile,
+    ) -> Result<(), Error> {
+        profile.validate()?;
+        self.store.dictionary_put(
+            named_keys::KYC_CONSUMER_PROFILES,
+            &consumer_package_hash.to_string(),
+            Some(profile),
+        );
+        Ok(())
+    }
+
+    /// Remove the profile of a consumer contract, whose checks are then answered with the global configuration.
+    pub fn remove_consumer_profile(&mut self, consumer_package_hash: ContractPackageHash) {
+        self.store.dictionary_put(
+            named_keys::KYC_CONSUMER_PROFILES,
+            &consumer_package_hash.to_string(),
+            Option::<ConsumerProfile>::None,
+        );
+    }
+
+    pub fn get_consumer_profile(
+        &self,
+        consumer_package_hash: ContractPackageHash,
+    ) -> Option<ConsumerProfile> {
+        self.store
+            .dictionary_get::<Option<ConsumerProfile>>(
+                named_keys::KYC_CONSUMER_PROFILES,
+                &consumer_package_hash.to_string(),
+            )
+            .flatten()
+    }
+
+    /// Turn usage metering on or off. Counting costs a dictionary write per provider asked.
+    pub fn set_usage_metering(&mut self, enabled: bool) {
+        self.store.write(named_keys::USAGE_METERING, enabled);
+        self.usage_metering = enabled;
+    }
+
+    pub fn usage_metering(&self) -> bool {
+        self.usage_metering
+    }
+
+    /// Start a new billing period, from which all usage counters start again at 0.
+    pub fn reset_usage(&mut self) {
+        let period = self.usage_period() + 1;
+        self.store.write(named_keys::USAGE_PERIOD, period);
+    }
+
+    /// Return the number of checks `consumer` called in the current billing period.
+    pub fn get_consumer_usage(&self, consumer: Consumer) -> u64 {
+        let (dictionary, item_key) = consumer_entry(
+            consumer,
+            named_keys::KYC_CONTRACT_CALLS,
+            named_keys::KYC_ACCOUNT_CALLS,
+        );
+        self.counter(dictionary, &item_key)
+    }
+
+    /// Return how often a provider was asked, and approved, in the current billing period.
+    pub fn get_provider_usage(&self, provider_package_hash: ContractPackageHash) -> ProviderUsage {
+        let str_provider = provider_package_hash.to_string();
+        ProviderUsage {
+            calls: self.counter(named_keys::KYC_PROVIDER_CALLS, &str_provider),
+            approvals: self.counter(named_keys::KYC_PROVIDER_APPROVALS, &str_provider),
+        }
+    }
+
+    /// Grant `account` admin rights over the registry, or revoke them.
+    pub fn set_admin(&mut self, account: AccountHash, is_admin: bool) {
+        self.store
+            .dictionary_put(named_keys::KYC_ADMINS, &account.to_string(), is_admin);
+    }
+
+    pub fn is_admin(&self, account: AccountHash) -> bool {
+        self.store
+            .dictionary_get::<bool>(named_keys::KYC_ADMINS, &account.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Check that `caller` is an admin, before it calls an admin entry point.
+    pub fn require_admin(&self, caller: AccountHash) -> Result<(), Error> {
+        if self.is_admin(caller) {
+            Ok(())
+        } else {
+            Err(Error::NotAdmin)
+        }
+    }
+
+    /// Check that `consumer`, called in a deploy of the account `caller`, may check `accounts`.
+    /// In restricted mode, only registered consumers may check any account.
+    /// In privacy mode, only consumer contracts with third-party access may check other accounts
+    /// than `caller`.
+    pub fn authorize_check(
+        &self,
+        caller: AccountHash,
+        consumer: Consumer,
+        accounts: &[Key],
+    ) -> Result<(), Error> {
+        if self.restricted_mode() && !self.is_consumer(consumer) {
+            return Err(Error::UnknownConsumer);
+        }
+        if !self.privacy_mode() {
+            return Ok(());
+        }
+        let third_party_access = match consumer {
+            Consumer::Contract(consumer_package_hash) => {
+                self.has_third_party_access(consumer_package_hash)
+            }
+            Consumer::Account(_) => false,
+        };
+        if third_party_access
+            || accounts
+                .iter()
+                .all(|account| *account == Key::Account(caller))
+        {
+            Ok(())
+        } else {
+            Err(Error::ThirdPartyCheckDenied)
+        }
+    }
+
+    /// Check account validity by asking all stored KYC providers sequentially, and return true on the first confirmation.
+    /// If all available providers refused return false,
+    pub fn is_kyc_proved<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        account: Key,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        self.meter_consumer_call();
+        let providers = self.active_providers(chain);
+        self.is_kyc_proved_by(caller, &providers, account, index, chain)
+    }
+
+    /// Check account validity like `is_kyc_proved`, but only ask providers that cover one of the given jurisdictions.
+    pub fn is_kyc_proved_in<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        account: Key,
+        index: Option<U256>,
+        jurisdictions: Vec<String>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        self.meter_consumer_call();
+        let providers: Vec<ActiveProvider> = self
+            .active_providers(chain)
+            .into_iter()
+            .filter(|provider| self.covers_any(provider.package_hash, &jurisdictions))
+            .collect();
+        self.is_kyc_proved_by(caller, &providers, account, index, chain)
+    }
+
+    /// Check account validity like `is_kyc_proved`, but only ask the given providers among the active ones.
+    /// Fails with `Error::UnknownProviders` if none of them is stored.
+    pub fn is_kyc_proved_with<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        account: Key,
+        index: Option<U256>,
+        providers: Vec<ContractPackageHash>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        if !providers
+            .iter()
+            .any(|provider_package_hash| self.is_stored(&provider_package_hash.to_string()))
+        {
+            return Err(Error::UnknownProviders);
+        }
+        self.meter_consumer_call();
+        let requested: Vec<ActiveProvider> = self
+            .active_providers(chain)
+            .into_iter()
+            .filter(|provider| providers.contains(&provider.package_hash))
+            .collect();
+        self.is_kyc_proved_by(caller, &requested, account, index, chain)
+    }
+
+    /// Check the validity of each account with the same resolved list of providers.
+    pub fn are_kyc_proved<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        accounts: Vec<Key>,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> Result<Vec<bool>, Error> {
+        self.meter_consumer_call();
+        let providers = self.active_providers(chain);
+        accounts
+            .into_iter()
+            .map(|account| self.is_kyc_proved_by(caller, &providers, account, index, chain))
+            .collect()
+    }
+
+    /// Check whether every account is valid, returning false on the first invalid account.
+    pub fn all_kyc_proved<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        accounts: Vec<Key>,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        self.meter_consumer_call();
+        let providers = self.active_providers(chain);
+        for account in accounts {
+            if !self.is_kyc_proved_by(caller, &providers, account, index, chain)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check whether any account is valid, returning true on the first valid account.
+    pub fn any_kyc_proved<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        accounts: Vec<Key>,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        self.meter_consumer_call();
+        let providers = self.active_providers(chain);
+        for account in accounts {
+            if self.is_kyc_proved_by(caller, &providers, account, index, chain)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Return the providers asked in checks, in the order they were stored: the unbanned ones,
+    /// without those suspended beyond the block time or not trusted by the consumer's profile.
+    /// A check that already passed through this proxy gets no providers, which breaks cycles of nested proxies.
+    pub fn active_providers(&self, chain: &ProxyChain) -> Vec<ActiveProvider> {
+        if chain.is_cycle(self.own_package_hash) {
+            return Vec::new();
+        }
+        let mut providers = self.stored_active_providers();
+        providers.retain(|provider| {
+            provider
+                .suspended_until
+                .map_or(true, |until| until <= self.blocktime)
+                && self.profile.trusts(provider)
+        });
+        providers
+    }
+
+    /// Ask the given providers sequentially, and return true once as many as the threshold say they have approved
+    /// the account, or else its primary account if it is a linked secondary account.
+    fn is_kyc_proved_by<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        providers: &[ActiveProvider],
+        account: Key,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        if self.is_kyc_proved_by_any(caller, providers, account, index, chain)? {
+            return Ok(true);
+        }
+        let primary = match account {
+            Key::Account(secondary) => self.get_primary_account(secondary),
+            _ => None,
+        };
+        match primary {
+            Some(primary) => {
+                self.is_kyc_proved_by_any(caller, providers, Key::Account(primary), index, chain)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Ask the given providers sequentially, and return true once as many as the threshold say they have approved
+    /// the account. Providers whose normalization does not accept the account are skipped, and the check fails
+    /// with `Error::UnsupportedAccountKey` if there were providers to ask but none accepts it.
+    fn is_kyc_proved_by_any<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        providers: &[ActiveProvider],
+        account: Key,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> Result<bool, Error> {
+        let mut asked = false;
+        let mut approvals = 0u8;
+        for provider in providers {
+            let account = match provider.normalization.normalize(account) {
+                Ok(account) => account,
+                Err(_) => continue,
+            };
+            asked = true;
+            if self.is_kyc_proved_single(caller, provider, account, index, chain) {
+                approvals += 1;
+                if approvals >= self.profile.threshold {
+                    return Ok(true);
+                }
+            }
+        }
+        if asked || providers.is_empty() {
+            Ok(false)
+        } else {
+            Err(Error::UnsupportedAccountKey)
+        }
+    }
+
+    /// Ask a single provider.
+    /// Nested proxies are passed the extended proxy chain, and are not asked if the maximum depth is reached.
+    fn is_kyc_proved_single<C: ProviderCaller>(
+        &mut self,
+        caller: &mut C,
+        provider: &ActiveProvider,
+        account: Key,
+        index: Option<U256>,
+        chain: &ProxyChain,
+    ) -> bool {
+        let approved = if provider.is_proxy {
+            match chain.next_hop(self.own_package_hash, self.max_proxy_depth()) {
+                Some(next_hop) => caller.is_kyc_proved(provider, account, index, Some(&next_hop)),
+                None => return false,
+            }
+        } else {
+            caller.is_kyc_proved(provider, account, index, None)
+        };
+        if self.usage_metering {
+            let str_provider = provider.package_hash.to_string();
+            self.increment(named_keys::KYC_PROVIDER_CALLS, &str_provider);
+            if approved {
+                self.increment(named_keys::KYC_PROVIDER_APPROVALS, &str_provider);
+            }
+        }
+        approved
+    }
+
+    /// Count a check of the consumer the registry was opened with, if usage is metered.
+    fn meter_consumer_call(&mut self) {
+        if !self.usage_metering {
+            return;
+        }
+        if let Some(consumer) = self.consumer {
+            let (dictionary, item_key) = consumer_entry(
+                consumer,
+                named_keys::KYC_CONTRACT_CALLS,
+                named_keys::KYC_ACCOUNT_CALLS,
+            );
+            self.increment(dictionary, &item_key);
+        }
+    }
+
+    fn usage_period(&self) -> u32 {
+        self.store
+            .read(named_keys::USAGE_PERIOD)
+            .unwrap_or_default()
+    }
+
+    /// Value of a usage counter in the current billing period.
+    fn counter(&self, dictionary: &str, item_key: &str) -> u64 {
+        self.counter_in(self.usage_period(), dictionary, item_key)
+    }
+
+    /// Value of a usage counter in `period`. Counters are stored with the billing period they count,
+    /// so those of earlier periods read as 0.
+    fn counter_in(&self, period: u32, dictionary: &str, item_key: &str) -> u64 {
+        match self
+            .store
+            .dictionary_get::<(u32, u64)>(dictionary, item_key)
+        {
+            Some((counted_period, count)) if counted_period == period => count,
+            _ => 0,
+        }
+    }
+
+    fn increment(&mut self, dictionary: &str, item_key: &str) {
+        let period = self.usage_period();
+        let count = self.counter_in(period, dictionary, item_key) + 1;
+        self.store
+            .dictionary_put(dictionary, item_key, (period, count));
+    }
+
+    /// Check that `secondary` can be linked to `primary`: links are one level deep, so neither
+    /// account may already be a secondary account, nor `secondary` a primary one.
+    fn check_link(&self, primary: AccountHash, secondary: AccountHash) -> Result<(), Error> {
+        if primary == secondary
+            || self.get_primary_account(primary).is_some()
+            || self.get_primary_account(secondary).is_some()
+            || !self.get_linked_accounts(secondary).is_empty()
+        {
+            return Err(Error::InvalidLink);
+        }
+        let max_links = u64::from(self.max_links_per_primary());
+        if self.get_linked_accounts(primary).len() as u64 >= max_links {
+            return Err(Error::LinkLimitReached);
+        }
+        Ok(())
+    }
+
+    /// The "kyc_active_providers" list, which is only missing while storage of the second schema
+    /// is migrated, before the list is built.
+    fn stored_active_providers(&self) -> Vec<ActiveProvider> {
+        self.store
+            .read(named_keys::KYC_ACTIVE_PROVIDERS)
+            .unwrap_or_default()
+    }
+
+    /// Entry of a stored provider in the "kyc_active_providers" list, from its dictionaries.
+    fn list_entry(
+        &self,
+        provider_package_hash: ContractPackageHash,
+        suspended_until: Option<u64>,
+    ) -> ActiveProvider {
+        let str_provider = provider_package_hash.to_string();
+        ActiveProvider {
+            package_hash: provider_package_hash,
+            is_proxy: self.is_proxy(provider_package_hash),
+            suspended_until,
+            adapter: self.adapter(&str_provider),
+            hash: self
+                .addressing(&str_provider)
+                .provider_hash(provider_package_hash),
+            normalization: self.normalization(&str_provider),
+            tier: self.tier(&str_provider),
+        }
+    }
+
+    /// Change the entry of a provider in the "kyc_active_providers" list, if it is listed.
+    fn update_list_entry<F: FnOnce(&mut ActiveProvider)>(
+        &mut self,
+        provider_package_hash: ContractPackageHash,
+        update: F,
+    ) {
+        let mut providers = self.stored_active_providers();
+        if let Some(provider) = providers
+            .iter_mut()
+            .find(|provider| provider.package_hash == provider_package_hash)
+        {
+            update(provider);
+            self.store
+                .write(named_keys::KYC_ACTIVE_PROVIDERS, providers);
+        }
+    }
+
+    /// Build the "kyc_active_providers" list from the dictionaries.
+    fn rebuild_active_providers(&mut self) {
+        let mut providers = Vec::new();
+        for provider_index in 0..self.len {
+            let provider_package_hash: ContractPackageHash = self
+                .store
+                .dictionary_get(named_keys::KYC_PROVIDER_INDEX, &provider_index.to_string())
+                .expect("indexed provider should be stored");
+            let status = self
+                .record(&provider_package_hash.to_string())
+                .expect("indexed provider should have a record")
+                .status;
+            if !status.is_banned() {
+                providers.push(self.list_entry(provider_package_hash, status.suspended_until()));
+            }
+        }
+        self.store
+            .write(named_keys::KYC_ACTIVE_PROVIDERS, providers);
+    }
+
+    fn adapter(&self, str_provider: &str) -> ProviderAdapter {
+        self.store
+            .dictionary_get(named_keys::KYC_PROVIDER_ADAPTERS, str_provider)
+            .unwrap_or_default()
+    }
+
+    fn addressing(&self, str_provider: &str) -> ProviderAddressing {
+        self.store
+            .dictionary_get(named_keys::KYC_PROVIDER_ADDRESSING, str_provider)
+            .unwrap_or_default()
+    }
+
+    fn normalization(&self, str_provider: &str) -> AccountNormalization {
+        self.store
+            .dictionary_get(named_keys::KYC_PROVIDER_NORMALIZATION, str_provider)
+            .unwrap_or_default()
+    }
+
+    fn tier(&self, str_provider: &str) -> u8 {
+        self.store
+            .dictionary_get(named_keys::KYC_PROVIDER_TIERS, str_provider)
+            .unwrap_or_default()
+    }
+
+    fn record(&self, str_provider: &str) -> Option<ProviderRecord> {
+        self.store
+            .dictionary_get(named_keys::KYC_PROVIDER_RECORDS, str_provider)
+    }
+
+    fn is_stored(&self, str_provider: &str) -> bool {
+        self.record(str_provider).is_some()
+    }
+}
+
+/// Copy the providers of the first schema's "kyc_providers" dictionary into the
+/// "kyc_provider_index" and "kyc_provider_records" dictionaries, with a copy of `registered`
+/// as metadata record, and remove it. A provider set inactive in it is banned with no reason.
+fn migrate_v1_providers<S: ProviderStore>(store: &mut S, registered: ProviderMetadata) {
+    let v1_len: u64 = store
+        .dictionary_get(named_keys::KYC_PROVIDERS, named_keys::LEN)
+        .unwrap_or_default();
+    let mut len = 0u64;
+    for v1_index in 0..v1_len {
+        let provider_package_hash = match store
+            .dictionary_get::<ContractPackageHash>(named_keys::KYC_PROVIDERS, &v1_index.to_string())
+        {
+            Some(provider_package_hash) => provider_package_hash,
+            None => continue,
+        };
+        let str_provider = provider_package_hash.to_string();
+        let active = store
+            .dictionary_get::<bool>(named_keys::KYC_PROVIDERS, &str_provider)
+            .unwrap_or_default();
+        let status = if active {
+            ProviderStatus::Active
+        } else {
+            ProviderStatus::Banned {
+                reason: String::new(),
+            }
+        };
+        store.dictionary_put(
+            named_keys::KYC_PROVIDER_INDEX,
+            &len.to_string(),
+            provider_package_hash,
+        );
+        store.dictionary_put(
+            named_keys::KYC_PROVIDER_RECORDS,
+            &str_provider,
+            ProviderRecord { index: len, status },
+        );
+        store.dictionary_put(
+            named_keys::KYC_PROVIDER_METADATA,
+            &str_provider,
+            registered.clone(),
+        );
+        len += 1;
+    }
+    store.write(named_keys::KYC_PROVIDER_COUNT, len);
+    store.remove(named_keys::KYC_PROVIDERS);
+}
+
+/// The dictionary and item key a value of `consumer` is stored under, given the dictionaries of
+/// consumer contracts and of consumer accounts.
+fn consumer_entry(
+    consumer: Consumer,
+    contracts: &'static str,
+    accounts: &'static str,
+) -> (&'static str, String) {
+    match consumer {
+        Consumer::Account(account_hash) => (accounts, account_hash.to_string()),
+        Consumer::Contract(consumer_package_hash) => (contracts, consumer_package_hash.to_string()),
+    }
+}
+
+/// Create the dictionaries of an empty registry, with `admin` as the first admin in the
+/// "kyc_admins" dictionary, and the "max_proxy_depth" value.
+fn new_dictionaries<S: ProviderStore>(store: &mut S, admin: AccountHash) {
+    store.new_dictionary(named_keys::KYC_PROVIDER_INDEX);
+    store.new_dictionary(named_keys::KYC_PROVIDER_RECORDS);
+    store.new_dictionary(named_keys::KYC_PROVIDER_JURISDICTIONS);
+    store.new_dictionary(named_keys::KYC_PROVIDER_METADATA);
+    store.new_dictionary(named_keys::KYC_NESTED_PROXIES);
+    store.new_dictionary(named_keys::KYC_PROVIDER_ADAPTERS);
+    store.new_dictionary(named_keys::KYC_PROVIDER_ADDRESSING);
+    store.new_dictionary(named_keys::KYC_PROVIDER_NORMALIZATION);
+    store.new_dictionary(named_keys::KYC_LINK_PROPOSALS);
+    store.new_dictionary(named_keys::KYC_ACCOUNT_LINKS);
+    store.new_dictionary(named_keys::KYC_LINKED_ACCOUNTS);
+    store.new_dictionary(named_keys::KYC_THIRD_PARTY_ACCESS);
+    store.new_dictionary(named_keys::KYC_CONSUMER_CONTRACTS);
+    store.new_dictionary(named_keys::KYC_CONSUMER_ACCOUNTS);
+    store.new_dictionary(named_keys::KYC_PROVIDER_TIERS);
+    store.new_dictionary(named_keys::KYC_CONSUMER_PROFILES);
+    store.new_dictionary(named_keys::KYC_CONTRACT_CALLS);
+    store.new_dictionary(named_keys::KYC_ACCOUNT_CALLS);
+    store.new_dictionary(named_keys::KYC_PROVIDER_CALLS);
+    store.new_dictionary(named_keys::KYC_PROVIDER_APPROVALS);
+    store.new_dictionary(named_keys::KYC_ADMINS);
+    store.dictionary_put(named_keys::KYC_ADMINS, &admin.to_string(), true);
+    store.write(named_keys::MAX_PROXY_DEPTH, DEFAULT_MAX_PROXY_DEPTH);
+}
+
+/// Convert a provider `Key` to its `ContractPackageHash`.
+pub fn provider_package_hash(provider_key: Key) -> Result<ContractPackageHash, Error> {
+    match provider_key {
+        Key::Hash(provider_hash) => Ok(ContractPackageHash::from(provider_hash)),
+        _ => Err(Error::InvalidProviderKey),
+    }
+}
@@ -0,0 +1,33 @@
+//! Storage the registry keeps its state in.
+
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped,
+};
+
+/// Named dictionaries and named values of the proxy contract.
+///
+/// The contract stores them in global state under its named keys, and reverts on failure; other
+/// implementations panic instead.
+pub trait ProviderStore {
+    /// Create the dictionary `name`.
+    fn new_dictionary(&mut self, name: &str);
+
+    /// Read the entry `key` of the dictionary `name`, or `None` if there is no such entry.
+    fn dictionary_get<T: CLTyped + FromBytes>(&self, name: &str, key: &str) -> Option<T>;
+
+    /// Write the entry `key` of the dictionary `name`.
+    fn dictionary_put<T: CLTyped + ToBytes>(&mut self, name: &str, key: &str, value: T);
+
+    /// Read the value `name`, or `None` if it was never written.
+    fn read<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T>;
+
+    /// Write the value `name`, creating it if needed.
+    fn write<T: CLTyped + ToBytes>(&mut self, name: &str, value: T);
+
+    /// Check whether a dictionary or value `name` exists.
+    fn contains(&self, name: &str) -> bool;
+
+    /// Forget the dictionary or value `name`.
+    fn remove(&mut self, name: &str);
+}
@@ -0,0 +1,45 @@
+//! Usage counters of the consumers and providers, kept while usage metering is on.
+
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Usage of a provider in the current billing period.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProviderUsage {
+    /// Number of times the provider was asked.
+    pub calls: u64,
+    /// Number of times it approved the account it was asked about.
+    pub approvals: u64,
+}
+
+/// The usage is serialized like `(calls, approvals)` would be, so it is typed as that tuple.
+impl CLTyped for ProviderUsage {
+    fn cl_type() -> CLType {
+        <(u64, u64)>::cl_type()
+    }
+}
+
+impl ToBytes for ProviderUsage {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.calls.to_bytes()?);
+        result.append(&mut self.approvals.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.calls.serialized_length() + self.approvals.serialized_length()
+    }
+}
+
+impl FromBytes for ProviderUsage {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (calls, bytes) = u64::from_bytes(bytes)?;
+        let (approvals, bytes) = u64::from_bytes(bytes)?;
+        Ok((ProviderUsage { calls, approvals }, bytes))
+    }
+}
@@ -0,0 +1,1143 @@
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, ContractHash, ContractPackageHash, Key,
+};
+use kyc_proxy_core::{
+    memory::{MemoryCaller, MemoryStore},
+    names::named_keys,
+    provider_package_hash, AccountNormalization, ActiveProvider, CallSpec, Consumer,
+    ConsumerProfile, Error, ProviderAdapter, ProviderAddressing, ProviderHash, ProviderMetadata,
+    ProviderMetadataUpdate, ProviderRecord, ProviderStatus, ProviderStore, ProviderUsage,
+    ProxyChain, Registry, ReturnKind, CURRENT_SCHEMA_VERSION, DEFAULT_MAX_PROXY_DEPTH,
+};
+
+fn provider(byte: u8) -> ContractPackageHash {
+    ContractPackageHash::new([byte; 32])
+}
+
+/// Package hash of the proxy owning the registry.
+fn proxy() -> ContractPackageHash {
+    provider(0)
+}
+
+fn account_hash(byte: u8) -> AccountHash {
+    AccountHash::new([byte; 32])
+}
+
+fn account(byte: u8) -> Key {
+    Key::Account(account_hash(byte))
+}
+
+/// Account installing, and migrating, the proxy in the tests.
+fn admin() -> AccountHash {
+    account_hash(9)
+}
+
+fn registered() -> ProviderMetadata {
+    ProviderMetadata::new(1_000, account(9))
+}
+
+fn registry(initial_providers: Vec<ContractPackageHash>) -> Registry<MemoryStore> {
+    Registry::init(
+        MemoryStore::new(),
+        proxy(),
+        admin(),
+        initial_providers,
+        registered(),
+    )
+    .unwrap()
+}
+
+fn add(registry: &mut Registry<MemoryStore>, provider: ContractPackageHash) {
+    registry
+        .add_kyc_provider(provider, registered(), ProviderMetadataUpdate::default())
+        .unwrap();
+}
+
+fn active(registry: &Registry<MemoryStore>) -> Vec<ContractPackageHash> {
+    registry
+        .active_providers(&ProxyChain::default())
+        .into_iter()
+        .map(|provider| provider.package_hash)
+        .collect()
+}
+
+#[test]
+fn test_init_stores_initial_providers() {
+    let registry = registry(vec![provider(1), provider(2)]);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(active(&registry), vec![provider(1), provider(2)]);
+    assert_eq!(registry.get_provider_info(provider(1)), Some(registered()));
+}
+
+#[test]
+fn test_init_rejects_self_reference() {
+    let result = Registry::init(
+        MemoryStore::new(),
+        proxy(),
+        admin(),
+        vec![proxy()],
+        registered(),
+    );
+    assert_eq!(result.err(), Some(Error::SelfReference));
+}
+
+#[test]
+fn test_add_rejects_own_contract_hash() {
+    let mut registry = registry(vec![]).with_own_contract_hash(ContractHash::new([8; 32]));
+    let result =
+        registry.add_kyc_provider(provider(8), registered(), ProviderMetadataUpdate::default());
+    assert_eq!(result, Err(Error::SelfReference));
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_add_is_idempotent() {
+    let mut registry = registry(vec![]);
+    add(&mut registry, provider(1));
+    add(&mut registry, provider(1));
+    assert_eq!(registry.len(), 1);
+    assert_eq!(
+        registry
+            .store()
+            .dictionary_keys(named_keys::KYC_PROVIDER_INDEX),
+        vec!["0".to_string()]
+    );
+}
+
+#[test]
+fn test_add_rejects_self_reference() {
+    let mut registry = registry(vec![]);
+    let result =
+        registry.add_kyc_provider(proxy(), registered(), ProviderMetadataUpdate::default());
+    assert_eq!(result, Err(Error::SelfReference));
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_reopen_keeps_len() {
+    let mut registry = registry(vec![provider(1)]);
+    add(&mut registry, provider(2));
+    let registry = Registry::open(registry.into_store(), proxy()).unwrap();
+    assert_eq!(registry.len(), 2);
+    assert_eq!(active(&registry), vec![provider(1), provider(2)]);
+}
+
+#[test]
+fn test_open_without_provider_count_fails() {
+    let mut store = registry(vec![provider(1)]).into_store();
+    store.remove(named_keys::KYC_PROVIDER_COUNT);
+    let result = Registry::open(store, proxy());
+    assert_eq!(result.err(), Some(Error::UninitializedRegistry));
+}
+
+#[test]
+fn test_ban_and_unban_keep_order() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3)]);
+    registry.ban_provider(provider(2), "fraud".to_string());
+    assert_eq!(active(&registry), vec![provider(1), provider(3)]);
+    registry.unban_provider(provider(2));
+    assert_eq!(
+        active(&registry),
+        vec![provider(1), provider(2), provider(3)]
+    );
+}
+
+#[test]
+fn test_ban_unknown_provider_is_ignored() {
+    let mut registry = registry(vec![provider(1)]);
+    registry.ban_provider(provider(2), "fraud".to_string());
+    registry.unban_provider(provider(2));
+    assert_eq!(registry.len(), 1);
+    assert_eq!(
+        registry.store().dictionary_get::<ProviderRecord>(
+            named_keys::KYC_PROVIDER_RECORDS,
+            &provider(2).to_string()
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_unban_restores_registration_order() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3), provider(4)]);
+    registry.ban_provider(provider(3), "fraud".to_string());
+    registry.ban_provider(provider(1), "fraud".to_string());
+    registry.unban_provider(provider(3));
+    registry.unban_provider(provider(3));
+    assert_eq!(
+        active(&registry),
+        vec![provider(2), provider(3), provider(4)]
+    );
+    registry.unban_provider(provider(1));
+    assert_eq!(
+        active(&registry),
+        vec![provider(1), provider(2), provider(3), provider(4)]
+    );
+}
+
+#[test]
+fn test_active_list_keeps_proxy_flag() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.set_provider_is_proxy(provider(2), true);
+    registry.ban_provider(provider(2), "fraud".to_string());
+    assert_eq!(
+        registry
+            .store()
+            .read::<Vec<ActiveProvider>>(named_keys::KYC_ACTIVE_PROVIDERS),
+        Some(vec![ActiveProvider {
+            package_hash: provider(1),
+            is_proxy: false,
+            suspended_until: None,
+            adapter: ProviderAdapter::KycProvider,
+            hash: ProviderHash::Package(provider(1)),
+            normalization: AccountNormalization::Verbatim,
+            tier: 0,
+        }])
+    );
+    registry.unban_provider(provider(2));
+    assert!(registry.active_providers(&ProxyChain::default())[1].is_proxy);
+}
+
+#[test]
+fn test_check_stops_at_first_approval() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3)]);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(2), account(1));
+    assert!(registry
+        .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(1), provider(2)]);
+}
+
+#[test]
+fn test_banned_provider_is_not_asked() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.ban_provider(provider(1), "fraud".to_string());
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(2)]);
+}
+
+#[test]
+fn test_check_in_jurisdictions() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.set_provider_jurisdictions(provider(1), vec!["de".to_string()]);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(2), account(1));
+    assert!(!registry
+        .is_kyc_proved_in(
+            &mut caller,
+            account(1),
+            None,
+            vec!["DE".to_string()],
+            &ProxyChain::default()
+        )
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(1)]);
+}
+
+#[test]
+fn test_check_with_requested_providers() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3)]);
+    registry.ban_provider(provider(3), "fraud".to_string());
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    caller.approve(provider(3), account(1));
+    assert!(!registry
+        .is_kyc_proved_with(
+            &mut caller,
+            account(1),
+            None,
+            vec![provider(2), provider(3), provider(4)],
+            &ProxyChain::default(),
+        )
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(2)]);
+    assert!(registry
+        .is_kyc_proved_with(
+            &mut caller,
+            account(1),
+            None,
+            vec![provider(1)],
+            &ProxyChain::default(),
+        )
+        .unwrap());
+    assert_eq!(
+        registry.is_kyc_proved_with(
+            &mut caller,
+            account(1),
+            None,
+            vec![provider(4)],
+            &ProxyChain::default(),
+        ),
+        Err(Error::UnknownProviders)
+    );
+}
+
+#[test]
+fn test_batch_checks() {
+    let mut registry = registry(vec![provider(1)]);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    let chain = ProxyChain::default();
+    assert_eq!(
+        registry
+            .are_kyc_proved(&mut caller, vec![account(1), account(2)], None, &chain)
+            .unwrap(),
+        vec![true, false]
+    );
+    assert!(!registry
+        .all_kyc_proved(&mut caller, vec![account(1), account(2)], None, &chain)
+        .unwrap());
+    assert!(registry
+        .any_kyc_proved(&mut caller, vec![account(2), account(1)], None, &chain)
+        .unwrap());
+}
+
+#[test]
+fn test_nested_proxy_gets_extended_chain() {
+    let mut registry = registry(vec![provider(1)]);
+    registry.set_provider_is_proxy(provider(1), true);
+    let mut caller = MemoryCaller::new();
+    registry
+        .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+        .unwrap();
+    assert_eq!(
+        caller.calls[0].chain,
+        Some(ProxyChain {
+            hops: 1,
+            visited: vec![proxy()],
+        })
+    );
+}
+
+#[test]
+fn test_nested_proxy_depth_and_cycles() {
+    let mut registry = registry(vec![provider(1)]);
+    registry.set_provider_is_proxy(provider(1), true);
+    registry.set_max_proxy_depth(1);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    let at_max_depth = ProxyChain {
+        hops: 1,
+        visited: vec![provider(5)],
+    };
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(1), None, &at_max_depth)
+        .unwrap());
+    let cycle = ProxyChain {
+        hops: 0,
+        visited: vec![proxy()],
+    };
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(1), None, &cycle)
+        .unwrap());
+    assert!(caller.calls.is_empty());
+}
+
+#[test]
+fn test_metadata_update() {
+    let mut registry = registry(vec![]);
+    registry
+        .add_kyc_provider(
+            provider(1),
+            registered(),
+            ProviderMetadataUpdate {
+                name: Some("first".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    registry.update_provider_metadata(
+        provider(1),
+        ProviderMetadataUpdate {
+            website: Some("https://first.example".to_string()),
+            ..Default::default()
+        },
+    );
+    let info = registry.get_provider_info(provider(1)).unwrap();
+    assert_eq!(info.name, "first");
+    assert_eq!(info.website, "https://first.example");
+    assert_eq!(registry.get_provider_info(provider(2)), None);
+}
+
+#[test]
+fn test_provider_key_conversion() {
+    assert_eq!(provider_package_hash(Key::Hash([1u8; 32])), Ok(provider(1)));
+    assert_eq!(
+        provider_package_hash(account(1)),
+        Err(Error::InvalidProviderKey)
+    );
+}
+
+/// Store of a proxy installed with the first storage schema, holding `providers` with their status.
+/// That schema only had the "kyc_providers" dictionary.
+fn v1_store(providers: &[(ContractPackageHash, bool)]) -> MemoryStore {
+    let mut store = MemoryStore::new();
+    store.new_dictionary(named_keys::KYC_PROVIDERS);
+    for (index, (provider, active)) in providers.iter().enumerate() {
+        store.dictionary_put(named_keys::KYC_PROVIDERS, &index.to_string(), *provider);
+        store.dictionary_put(named_keys::KYC_PROVIDERS, &provider.to_string(), *active);
+    }
+    store.dictionary_put(
+        named_keys::KYC_PROVIDERS,
+        named_keys::LEN,
+        providers.len() as u64,
+    );
+    store
+}
+
+#[test]
+fn test_open_v1_storage_fails() {
+    let result = Registry::open(v1_store(&[(provider(1), true)]), proxy());
+    assert_eq!(result.err(), Some(Error::OutdatedSchema));
+}
+
+#[test]
+fn test_migrate_v1_storage() {
+    let store = v1_store(&[
+        (provider(1), true),
+        (provider(2), false),
+        (provider(3), true),
+    ]);
+    let registry = Registry::migrate(store, proxy(), admin(), registered()).unwrap();
+    assert_eq!(registry.len(), 3);
+    assert_eq!(active(&registry), vec![provider(1), provider(3)]);
+    assert_eq!(
+        registry.get_provider_record(provider(2)),
+        Some(ProviderRecord {
+            index: 1,
+            status: ProviderStatus::Banned {
+                reason: String::new()
+            }
+        })
+    );
+    assert_eq!(registry.get_provider_info(provider(1)), Some(registered()));
+    assert_eq!(registry.max_proxy_depth(), DEFAULT_MAX_PROXY_DEPTH);
+    assert!(!registry.store().contains(named_keys::KYC_PROVIDERS));
+    assert!(registry.is_admin(admin()));
+    assert_eq!(
+        registry.store().read::<u8>(named_keys::SCHEMA_VERSION),
+        Some(CURRENT_SCHEMA_VERSION)
+    );
+
+    let mut registry = Registry::open(registry.into_store(), proxy()).unwrap();
+    registry.unban_provider(provider(2));
+    add(&mut registry, provider(4));
+    registry.set_provider_is_proxy(provider(3), true);
+    registry.set_provider_jurisdictions(provider(1), vec!["DE".to_string()]);
+    registry.update_provider_metadata(
+        provider(1),
+        ProviderMetadataUpdate {
+            name: Some("first".to_string()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        active(&registry),
+        vec![provider(1), provider(2), provider(3), provider(4)]
+    );
+    assert!(registry.is_proxy(provider(3)));
+    assert_eq!(
+        registry.get_provider_info(provider(1)).unwrap().name,
+        "first"
+    );
+    assert_eq!(registry.get_provider_info(provider(4)), Some(registered()));
+}
+
+#[test]
+fn test_migrated_v1_storage_matches_init() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3)]);
+    registry.ban_provider(provider(2), String::new());
+    let store = v1_store(&[
+        (provider(1), true),
+        (provider(2), false),
+        (provider(3), true),
+    ]);
+    let migrated = Registry::migrate(store, proxy(), admin(), registered()).unwrap();
+    assert_eq!(migrated.store(), registry.store());
+}
+
+#[test]
+fn test_provider_adapter() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry
+        .set_provider_adapter(provider(2), ProviderAdapter::Cep47)
+        .unwrap();
+    registry
+        .set_provider_adapter(provider(3), ProviderAdapter::Cep78)
+        .unwrap();
+    assert_eq!(
+        registry.get_provider_adapter(provider(1)),
+        Some(ProviderAdapter::KycProvider)
+    );
+    assert_eq!(
+        registry.get_provider_adapter(provider(2)),
+        Some(ProviderAdapter::Cep47)
+    );
+    assert_eq!(registry.get_provider_adapter(provider(3)), None);
+
+    // the adapter is kept while the provider is banned
+    registry.ban_provider(provider(2), String::new());
+    registry.unban_provider(provider(2));
+    let adapters: Vec<ProviderAdapter> = registry
+        .active_providers(&ProxyChain::default())
+        .into_iter()
+        .map(|provider| provider.adapter)
+        .collect();
+    assert_eq!(
+        adapters,
+        vec![ProviderAdapter::KycProvider, ProviderAdapter::Cep47]
+    );
+}
+
+#[test]
+fn test_provider_addressing() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.set_provider_addressing(provider(2), ProviderAddressing::Contract);
+    registry.set_provider_addressing(provider(3), ProviderAddressing::Contract);
+    assert_eq!(
+        registry.get_provider_addressing(provider(1)),
+        Some(ProviderAddressing::Package)
+    );
+    assert_eq!(
+        registry.get_provider_addressing(provider(2)),
+        Some(ProviderAddressing::Contract)
+    );
+    assert_eq!(registry.get_provider_addressing(provider(3)), None);
+
+    // the addressing is kept while the provider is banned
+    registry.ban_provider(provider(2), String::new());
+    registry.unban_provider(provider(2));
+    let hashes: Vec<ProviderHash> = registry
+        .active_providers(&ProxyChain::default())
+        .into_iter()
+        .map(|provider| provider.hash)
+        .collect();
+    assert_eq!(
+        hashes,
+        vec![
+            ProviderHash::Package(provider(1)),
+            ProviderHash::Contract(ContractHash::new([2; 32]))
+        ]
+    );
+}
+
+#[test]
+fn test_account_normalization() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.set_provider_normalization(provider(2), AccountNormalization::AccountOnly);
+    assert_eq!(
+        registry.get_provider_normalization(provider(1)),
+        Some(AccountNormalization::Verbatim)
+    );
+    assert_eq!(
+        registry.get_provider_normalization(provider(2)),
+        Some(AccountNormalization::AccountOnly)
+    );
+    assert_eq!(registry.get_provider_normalization(provider(3)), None);
+
+    let contract = Key::Hash([1; 32]);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), contract);
+    caller.approve(provider(2), account(1));
+    let chain = ProxyChain::default();
+    // the second provider is skipped for a contract key, and the first one still asked
+    assert!(registry
+        .is_kyc_proved(&mut caller, contract, None, &chain)
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(1)]);
+    assert!(registry
+        .is_kyc_proved(&mut caller, account(1), None, &chain)
+        .unwrap());
+
+    // rejected only if no provider accepts the key
+    registry.set_provider_normalization(provider(1), AccountNormalization::AccountOnly);
+    assert_eq!(
+        registry.is_kyc_proved(&mut caller, contract, None, &chain),
+        Err(Error::UnsupportedAccountKey)
+    );
+    assert_eq!(
+        registry.any_kyc_proved(&mut caller, vec![contract, account(1)], None, &chain),
+        Err(Error::UnsupportedAccountKey)
+    );
+    assert!(registry
+        .any_kyc_proved(&mut caller, vec![account(1), contract], None, &chain)
+        .unwrap());
+}
+
+#[test]
+fn test_linked_account_inherits_kyc() {
+    let mut registry = registry(vec![provider(1)]);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    let chain = ProxyChain::default();
+    assert_eq!(
+        registry.propose_link(&mut caller, account_hash(2), account_hash(3)),
+        Err(Error::PrimaryNotKycProved)
+    );
+    registry
+        .propose_link(&mut caller, account_hash(1), account_hash(2))
+        .unwrap();
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(2), None, &chain)
+        .unwrap());
+    assert_eq!(
+        registry.accept_link(account_hash(2), account_hash(3)),
+        Err(Error::InvalidLink)
+    );
+    registry
+        .accept_link(account_hash(2), account_hash(1))
+        .unwrap();
+    assert_eq!(
+        registry.get_primary_account(account_hash(2)),
+        Some(account_hash(1))
+    );
+    assert_eq!(
+        registry.get_linked_accounts(account_hash(1)),
+        vec![account_hash(2)]
+    );
+    assert!(registry
+        .is_kyc_proved(&mut caller, account(2), None, &chain)
+        .unwrap());
+
+    // revoking the primary account revokes the secondary one
+    caller.revoke(provider(1), account(1));
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(2), None, &chain)
+        .unwrap());
+
+    caller.approve(provider(1), account(1));
+    registry.unlink(account_hash(1), account_hash(2)).unwrap();
+    assert_eq!(registry.get_primary_account(account_hash(2)), None);
+    assert!(registry.get_linked_accounts(account_hash(1)).is_empty());
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(2), None, &chain)
+        .unwrap());
+    assert_eq!(
+        registry.unlink(account_hash(2), account_hash(1)),
+        Err(Error::InvalidLink)
+    );
+}
+
+#[test]
+fn test_link_limit_and_depth() {
+    let mut registry = registry(vec![provider(1)]);
+    registry.set_max_links_per_primary(1);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    caller.approve(provider(1), account(4));
+    registry
+        .propose_link(&mut caller, account_hash(1), account_hash(2))
+        .unwrap();
+    registry
+        .accept_link(account_hash(2), account_hash(1))
+        .unwrap();
+    assert_eq!(
+        registry.propose_link(&mut caller, account_hash(1), account_hash(3)),
+        Err(Error::LinkLimitReached)
+    );
+    // links are one level deep
+    assert_eq!(
+        registry.propose_link(&mut caller, account_hash(2), account_hash(3)),
+        Err(Error::InvalidLink)
+    );
+    assert_eq!(
+        registry.propose_link(&mut caller, account_hash(4), account_hash(1)),
+        Err(Error::InvalidLink)
+    );
+    assert_eq!(
+        registry.propose_link(&mut caller, account_hash(4), account_hash(4)),
+        Err(Error::InvalidLink)
+    );
+}
+
+#[test]
+fn test_privacy_mode() {
+    let mut registry = registry(vec![]);
+    let consumer = Consumer::Contract(provider(7));
+    let session = Consumer::Account(account_hash(1));
+    let third_party = [account(1), account(2)];
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &third_party),
+        Ok(())
+    );
+
+    registry.set_privacy_mode(true);
+    assert!(registry.privacy_mode());
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &[account(1)]),
+        Ok(())
+    );
+    assert_eq!(
+        registry.authorize_check(account_hash(1), session, &[account(1)]),
+        Ok(())
+    );
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &third_party),
+        Err(Error::ThirdPartyCheckDenied)
+    );
+
+    registry.set_third_party_access(provider(7), true);
+    assert!(registry.has_third_party_access(provider(7)));
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &third_party),
+        Ok(())
+    );
+    assert_eq!(
+        registry.authorize_check(account_hash(1), session, &third_party),
+        Err(Error::ThirdPartyCheckDenied)
+    );
+    registry.set_third_party_access(provider(7), false);
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &third_party),
+        Err(Error::ThirdPartyCheckDenied)
+    );
+}
+
+#[test]
+fn test_restricted_mode() {
+    let mut registry = registry(vec![]);
+    let consumer = Consumer::Contract(provider(7));
+    let session = Consumer::Account(account_hash(1));
+    registry.set_restricted_mode(true);
+    assert!(registry.restricted_mode());
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &[account(1)]),
+        Err(Error::UnknownConsumer)
+    );
+    assert_eq!(
+        registry.authorize_check(account_hash(1), session, &[account(1)]),
+        Err(Error::UnknownConsumer)
+    );
+
+    registry.add_consumer(consumer);
+    registry.add_consumer(session);
+    assert!(registry.is_consumer(consumer));
+    assert!(!registry.is_consumer(Consumer::Account(account_hash(7))));
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &[account(2)]),
+        Ok(())
+    );
+    assert_eq!(
+        registry.authorize_check(account_hash(1), session, &[account(1)]),
+        Ok(())
+    );
+
+    registry.set_privacy_mode(true);
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &[account(2)]),
+        Err(Error::ThirdPartyCheckDenied)
+    );
+
+    registry.remove_consumer(consumer);
+    assert!(!registry.is_consumer(consumer));
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &[account(1)]),
+        Err(Error::UnknownConsumer)
+    );
+    registry.set_restricted_mode(false);
+    assert_eq!(
+        registry.authorize_check(account_hash(1), consumer, &[account(1)]),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_consumer_profile() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3)]);
+    let consumer = provider(7);
+    assert_eq!(registry.get_consumer_profile(consumer), None);
+    registry.set_provider_tier(provider(2), 2);
+    registry.set_provider_tier(provider(3), 1);
+    assert_eq!(registry.get_provider_tier(provider(2)), Some(2));
+    assert_eq!(registry.get_provider_tier(provider(4)), None);
+    let profile = ConsumerProfile {
+        providers: Some(vec![provider(1), provider(2), provider(3)]),
+        threshold: 2,
+        min_tier: 1,
+    };
+    registry
+        .set_consumer_profile(consumer, profile.clone())
+        .unwrap();
+    assert_eq!(registry.get_consumer_profile(consumer), Some(profile));
+    let mut registry = registry.with_consumer(Consumer::Contract(consumer));
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    caller.approve(provider(2), account(1));
+    // provider 1 is below the minimum tier, so a single approval is left
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(2), provider(3)]);
+    caller.approve(provider(3), account(1));
+    assert!(registry
+        .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+        .unwrap());
+}
+
+#[test]
+fn test_consumer_profile_falls_back_to_global_configuration() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    let profile = ConsumerProfile {
+        providers: Some(vec![provider(2)]),
+        ..ConsumerProfile::default()
+    };
+    registry.set_consumer_profile(provider(7), profile).unwrap();
+    assert_eq!(
+        registry.set_consumer_profile(
+            provider(7),
+            ConsumerProfile {
+                threshold: 0,
+                ..ConsumerProfile::default()
+            }
+        ),
+        Err(Error::InvalidConsumerProfile)
+    );
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    let store = registry.store().clone();
+    for consumer in [
+        Consumer::Contract(provider(8)),
+        Consumer::Account(account_hash(7)),
+    ] {
+        let mut registry = Registry::open(store.clone(), proxy())
+            .unwrap()
+            .with_consumer(consumer);
+        assert!(registry
+            .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+            .unwrap());
+    }
+
+    registry.remove_consumer_profile(provider(7));
+    assert_eq!(registry.get_consumer_profile(provider(7)), None);
+    let mut registry = registry.with_consumer(Consumer::Contract(provider(7)));
+    assert!(registry
+        .is_kyc_proved(&mut caller, account(1), None, &ProxyChain::default())
+        .unwrap());
+}
+
+#[test]
+fn test_usage_metering() {
+    let consumer = Consumer::Contract(provider(7));
+    let mut registry = registry(vec![provider(1), provider(2)]).with_consumer(consumer);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(2), account(1));
+    let chain = ProxyChain::default();
+    registry
+        .is_kyc_proved(&mut caller, account(1), None, &chain)
+        .unwrap();
+    assert_eq!(registry.get_consumer_usage(consumer), 0);
+    assert_eq!(
+        registry.get_provider_usage(provider(2)),
+        ProviderUsage::default()
+    );
+
+    registry.set_usage_metering(true);
+    assert!(registry.usage_metering());
+    registry
+        .is_kyc_proved(&mut caller, account(1), None, &chain)
+        .unwrap();
+    registry
+        .are_kyc_proved(&mut caller, vec![account(1), account(2)], None, &chain)
+        .unwrap();
+    assert_eq!(registry.get_consumer_usage(consumer), 2);
+    assert_eq!(
+        registry.get_consumer_usage(Consumer::Account(account_hash(7))),
+        0
+    );
+    assert_eq!(
+        registry.get_provider_usage(provider(1)),
+        ProviderUsage {
+            calls: 3,
+            approvals: 0
+        }
+    );
+    assert_eq!(
+        registry.get_provider_usage(provider(2)),
+        ProviderUsage {
+            calls: 3,
+            approvals: 2
+        }
+    );
+
+    // the flag is kept in storage
+    let mut registry = Registry::open(registry.into_store(), proxy())
+        .unwrap()
+        .with_consumer(consumer);
+    assert!(registry.usage_metering());
+    registry.reset_usage();
+    assert_eq!(registry.get_consumer_usage(consumer), 0);
+    assert_eq!(
+        registry.get_provider_usage(provider(2)),
+        ProviderUsage::default()
+    );
+    registry
+        .is_kyc_proved(&mut caller, account(1), None, &chain)
+        .unwrap();
+    assert_eq!(registry.get_consumer_usage(consumer), 1);
+    assert_eq!(
+        registry.get_provider_usage(provider(2)),
+        ProviderUsage {
+            calls: 1,
+            approvals: 1
+        }
+    );
+}
+
+#[test]
+fn test_admins() {
+    let mut registry = registry(vec![]);
+    assert!(registry.is_admin(admin()));
+    assert_eq!(registry.require_admin(admin()), Ok(()));
+    assert_eq!(
+        registry.require_admin(account_hash(1)),
+        Err(Error::NotAdmin)
+    );
+    registry.set_admin(account_hash(1), true);
+    assert_eq!(registry.require_admin(account_hash(1)), Ok(()));
+    registry.set_admin(admin(), false);
+    assert!(!registry.is_admin(admin()));
+}
+
+#[test]
+fn test_consumer_from_key() {
+    assert_eq!(
+        Consumer::from_key(account(1)),
+        Ok(Consumer::Account(account_hash(1)))
+    );
+    assert_eq!(
+        Consumer::from_key(Key::Hash([7; 32])),
+        Ok(Consumer::Contract(provider(7)))
+    );
+    assert_eq!(
+        Consumer::from_key(Key::Dictionary([7; 32])),
+        Err(Error::InvalidConsumerKey)
+    );
+}
+
+#[test]
+fn test_provider_metadata_layout() {
+    let mut metadata = registered();
+    metadata.name = "First KYC".to_string();
+    let tuple: ((String, String, String), (String, u64, Key)) = (
+        (metadata.name.clone(), String::new(), String::new()),
+        (String::new(), metadata.added_at, metadata.added_by),
+    );
+    assert_eq!(metadata.to_bytes().unwrap(), tuple.to_bytes().unwrap());
+    assert_eq!(
+        ProviderMetadata::cl_type(),
+        <((String, String, String), (String, u64, Key))>::cl_type()
+    );
+}
+
+#[test]
+fn test_consumer_profile_layout() {
+    let profile = ConsumerProfile {
+        providers: Some(vec![provider(1)]),
+        threshold: 2,
+        min_tier: 1,
+    };
+    let tuple: (Option<Vec<ContractPackageHash>>, u8, u8) = (Some(vec![provider(1)]), 2, 1);
+    assert_eq!(profile.to_bytes().unwrap(), tuple.to_bytes().unwrap());
+    assert_eq!(
+        ConsumerProfile::cl_type(),
+        <(Option<Vec<ContractPackageHash>>, u8, u8)>::cl_type()
+    );
+}
+
+#[test]
+fn test_provider_usage_layout() {
+    let usage = ProviderUsage {
+        calls: 3,
+        approvals: 2,
+    };
+    assert_eq!(usage.to_bytes().unwrap(), (3u64, 2u64).to_bytes().unwrap());
+    assert_eq!(ProviderUsage::cl_type(), <(u64, u64)>::cl_type());
+}
+
+fn custom_adapter(index_arg: Option<&str>) -> ProviderAdapter {
+    ProviderAdapter::Custom(CallSpec {
+        entry_point: "is_verified".to_string(),
+        account_arg: "holder".to_string(),
+        index_arg: index_arg.map(str::to_string),
+        returns: ReturnKind::Bool,
+    })
+}
+
+#[test]
+fn test_custom_provider_adapter() {
+    let mut registry = registry(vec![provider(1)]);
+    let adapter = custom_adapter(Some("level"));
+    registry
+        .set_provider_adapter(provider(1), adapter.clone())
+        .unwrap();
+    assert_eq!(
+        registry.get_provider_adapter(provider(1)),
+        Some(adapter.clone())
+    );
+    assert_eq!(
+        registry.active_providers(&ProxyChain::default())[0].adapter,
+        adapter
+    );
+
+    let bytes = adapter.to_bytes().unwrap();
+    assert_eq!(bytes.len(), adapter.serialized_length());
+    assert_eq!(
+        ProviderAdapter::from_bytes(&bytes).unwrap(),
+        (adapter, &[][..])
+    );
+}
+
+#[test]
+fn test_token_call_specs() {
+    assert_eq!(
+        ProviderAdapter::Cep47.call_spec(),
+        CallSpec {
+            entry_point: "balance_of".to_string(),
+            account_arg: "owner".to_string(),
+            index_arg: None,
+            returns: ReturnKind::PositiveU256,
+        }
+    );
+    assert_eq!(
+        ProviderAdapter::Cep78.call_spec(),
+        CallSpec {
+            entry_point: "balance_of".to_string(),
+            account_arg: "token_owner".to_string(),
+            index_arg: None,
+            returns: ReturnKind::PositiveU64,
+        }
+    );
+}
+
+#[test]
+fn test_invalid_call_spec() {
+    let mut registry = registry(vec![provider(1)]);
+    let mut missing_entry_point = custom_adapter(None);
+    if let ProviderAdapter::Custom(call_spec) = &mut missing_entry_point {
+        call_spec.entry_point.clear();
+    }
+    for adapter in vec![missing_entry_point, custom_adapter(Some(""))] {
+        assert_eq!(
+            registry.set_provider_adapter(provider(1), adapter),
+            Err(Error::InvalidCallSpec)
+        );
+    }
+    assert_eq!(
+        registry.get_provider_adapter(provider(1)),
+        Some(ProviderAdapter::KycProvider)
+    );
+}
+
+#[test]
+fn test_set_provider_status() {
+    let mut registry = registry(vec![provider(1), provider(2), provider(3)]);
+    registry.set_provider_status(
+        provider(1),
+        ProviderStatus::Suspended {
+            until: 5_000,
+            reason: "audit".to_string(),
+        },
+    );
+    registry.set_provider_status(provider(2), ProviderStatus::Deprecated);
+    registry.set_provider_status(provider(4), ProviderStatus::Deprecated);
+    assert_eq!(active(&registry), vec![provider(2), provider(3)]);
+    assert_eq!(
+        registry.get_provider_status(provider(2)),
+        Some(ProviderStatus::Deprecated)
+    );
+    assert_eq!(registry.get_provider_status(provider(4)), None);
+
+    // the suspension ends at its block time
+    let registry = registry.with_blocktime(5_000);
+    assert_eq!(
+        active(&registry),
+        vec![provider(1), provider(2), provider(3)]
+    );
+}
+
+#[test]
+fn test_suspended_provider_is_not_asked() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.set_provider_status(
+        provider(1),
+        ProviderStatus::Suspended {
+            until: 5_000,
+            reason: "audit".to_string(),
+        },
+    );
+    registry.set_provider_status(provider(2), ProviderStatus::Deprecated);
+    let mut caller = MemoryCaller::new();
+    caller.approve(provider(1), account(1));
+    caller.approve(provider(2), account(2));
+    let mut registry = registry.with_blocktime(4_999);
+    let chain = ProxyChain::default();
+    assert!(!registry
+        .is_kyc_proved(&mut caller, account(1), None, &chain)
+        .unwrap());
+    assert!(registry
+        .is_kyc_proved(&mut caller, account(2), None, &chain)
+        .unwrap());
+    assert_eq!(caller.asked_providers(), vec![provider(2), provider(2)]);
+}
+
+#[test]
+fn test_unban_leaves_suspension() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    let suspended = ProviderStatus::Suspended {
+        until: 5_000,
+        reason: "audit".to_string(),
+    };
+    registry.set_provider_status(provider(2), suspended.clone());
+    registry.unban_provider(provider(2));
+    assert_eq!(registry.get_provider_status(provider(2)), Some(suspended));
+    let registry = registry.with_blocktime(4_999);
+    assert_eq!(active(&registry), vec![provider(1)]);
+}
+
+#[test]
+fn test_ban_replaces_suspension() {
+    let mut registry = registry(vec![provider(1), provider(2)]);
+    registry.set_provider_status(
+        provider(2),
+        ProviderStatus::Suspended {
+            until: 5_000,
+            reason: "audit".to_string(),
+        },
+    );
+    registry.ban_provider(provider(2), "fraud".to_string());
+    let registry = registry.with_blocktime(10_000);
+    assert_eq!(active(&registry), vec![provider(1)]);
+    assert_eq!(
+        registry.get_provider_status(provider(2)),
+        Some(ProviderStatus::Banned {
+            reason: "fraud".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_migrate_empty_v1_storage() {
+    let registry = Registry::migrate(v1_store(&[]), proxy(), admin(), registered()).unwrap();
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_migrate_current_storage_is_unchanged() {
+    let registry = registry(vec![provider(1)]);
+    let store = registry.into_store();
+    let registry = Registry::migrate(store.clone(), proxy(), admin(), registered()).unwrap();
+    assert_eq!(registry.store(), &store);
+}
+
+#[test]
+fn test_migrate_without_registry_fails() {
+    let result = Registry::migrate(MemoryStore::new(), proxy(), admin(), registered());
+    assert_eq!(result.err(), Some(Error::UnsupportedSchema));
+}
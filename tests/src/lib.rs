@@ -6,12 +6,34 @@ use casper_engine_test_support::{
     DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
 };
 
+use casper_execution_engine::shared::stored_value::StoredValue;
 use casper_execution_engine::storage::global_state::in_memory::InMemoryGlobalState;
+use casper_types::bytesrepr;
 use casper_types::system::mint;
 use casper_types::{account::AccountHash, runtime_args, PublicKey, RuntimeArgs, SecretKey, U512};
-use casper_types::{ContractHash, ContractPackageHash, Key};
+use casper_types::{ContractHash, ContractPackageHash, Key, URef};
+use kyc_proxy_client::names::{self, args, entry_points, named_keys};
+use kyc_proxy_client::registry::{self, DecodeError};
+use kyc_proxy_client::{
+    deploy_item, AccountNormalization, AddKycProvider, CallSpec, ConsumerProfile, MockAnswer,
+    MockProviderCall, ProviderAdapter, ProviderAddressing, ProviderCall, ProviderInstall,
+    ProviderMetadataFields, ProviderStatus, ProviderUsage, ProxyCall, ProxyInstall, RegistryEntry,
+    RegistrySnapshot, RegistryUrefs, ReturnKind, VerdictSession,
+};
+use proptest::prelude::*;
 use rand::Rng;
 
+/// Metadata record of a KYC provider as stored by the proxy.
+#[derive(Debug, PartialEq)]
+pub struct ProviderInfo {
+    pub name: String,
+    pub website: String,
+    pub contact: String,
+    pub description: String,
+    pub added_at: u64,
+    pub added_by: Key,
+}
+
 pub struct ProxyContract {
     pub builder: WasmTestBuilder<InMemoryGlobalState>,
     pub contract_hash: [u8; 32],
@@ -23,6 +45,16 @@ pub struct ProxyContract {
 
 impl ProxyContract {
     pub fn deploy() -> Self {
+        Self::install("kyc-proxy.wasm")
+    }
+
+    /// Install the proxy as first released, with the first storage schema, from `kyc_proxy_v1.wasm`.
+    pub fn deploy_v1() -> Self {
+        Self::install("kyc_proxy_v1.wasm")
+    }
+
+    /// Install the proxy of `code` under the name "kyc" from the admin account.
+    fn install(code: &str) -> Self {
         // We create 3 users. One to oversee and deploy the contract, one to send the payment
         // and one to receive it.
         let mut rng = rand::thread_rng();
@@ -37,11 +69,10 @@ impl ProxyContract {
         let participant_two_account_addr = AccountHash::from(&participant_two_public_key);
         let participant_three_account_addr = AccountHash::from(&participant_three_public_key);
 
-        let code = PathBuf::from("kyc-proxy.wasm");
-        let args = runtime_args! {"name"=> "kyc", "initial_providers"=> Option::<Vec<ContractPackageHash>>::None};
+        let code = PathBuf::from(code);
         let deploy = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_session_code(code, args)
+            .with_session_code(code, ProxyInstall::new("kyc").args())
             .with_address(admin_account_addr)
             .with_authorization_keys(&[admin_account_addr])
             .with_deploy_hash(rng.gen())
@@ -118,7 +149,7 @@ impl ProxyContract {
             .query(
                 None,
                 Key::Account(admin_account_addr),
-                &["kyc-proxy_contract_hash".to_string()],
+                &[names::contract_hash_key("kyc")],
             )
             .expect("should be stored value.")
             .as_cl_value()
@@ -131,8 +162,8 @@ impl ProxyContract {
                 None,
                 Key::Account(admin_account_addr),
                 &[
-                    "kyc-proxy_contract".to_string(),
-                    "kyc-proxy_contract_package".to_string(),
+                    names::contract_key("kyc"),
+                    names::contract_package_key("kyc"),
                 ],
             )
             .expect("should be stored value.")
@@ -159,26 +190,46 @@ impl ProxyContract {
         }
     }
 
+    /// Run `kyc-proxy.wasm` again under the name "kyc" from the admin account, which upgrades the
+    /// installed proxy to a new contract version and migrates its storage.
+    pub fn upgrade(&mut self) {
+        let mut rng = rand::thread_rng();
+        let code = PathBuf::from("kyc-proxy.wasm");
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(code, ProxyInstall::new("kyc").args())
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).expect_success().commit();
+        self.contract_hash = self
+            .builder
+            .query(
+                None,
+                Key::Account(self.admin_account.1),
+                &[names::contract_hash_key("kyc")],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be contract hash.");
+    }
+
+    /// Install a reference provider from `deployer`, who becomes its admin.
     pub fn deploy_kyc(
         &mut self,
         deployer: AccountHash,
         kyc_name: &str,
     ) -> (ContractPackageHash, ContractHash) {
         let mut rng = rand::thread_rng();
-        let kyc_code = PathBuf::from("kyc-contract.wasm");
-        let mut meta = BTreeMap::new();
-        meta.insert("origin".to_string(), "kyc".to_string());
-
-        let kyc_args = runtime_args! {
-            "name" => kyc_name,
-            "contract_name" => kyc_name,
-            "symbol" => "symbol",
-            "meta" => meta,
-            "admin" => Key::Account(deployer)
-        };
+        let kyc_code = PathBuf::from("kyc-provider.wasm");
         let kyc_session = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_session_code(kyc_code, kyc_args)
+            .with_session_code(kyc_code, ProviderInstall::new(kyc_name).args())
             .with_address(deployer)
             .with_authorization_keys(&[deployer])
             .with_deploy_hash(rng.gen())
@@ -190,47 +241,248 @@ impl ProxyContract {
                 .query(
                     None,
                     Key::Account(deployer),
-                    &[format!("{}_package_hash_wrapped", kyc_name)],
+                    &[names::provider::package_hash_key(kyc_name)],
                 )
                 .expect("should be stored value.")
                 .as_cl_value()
                 .expect("should be cl value.")
                 .clone()
                 .into_t()
-                .expect("should be string."),
+                .expect("should be package hash."),
             self.builder
                 .query(
                     None,
                     Key::Account(deployer),
-                    &[format!("{}_contract_hash_wrapped", kyc_name)],
+                    &[names::provider::contract_hash_key(kyc_name)],
                 )
                 .expect("should be stored value.")
                 .as_cl_value()
                 .expect("should be cl value.")
                 .clone()
                 .into_t()
-                .expect("should be string."),
+                .expect("should be contract hash."),
         )
     }
 
-    pub fn add_kyc(&mut self, deployer: AccountHash, kyc_hash: [u8; 32], recipient: AccountHash) {
+    /// Install the CEP-47 KYC token of `kyc-contract.wasm` from `deployer`, who becomes its admin.
+    pub fn deploy_kyc_token(
+        &mut self,
+        deployer: AccountHash,
+        token_name: &str,
+    ) -> (ContractPackageHash, ContractHash) {
+        let mut rng = rand::thread_rng();
+        let mut meta = BTreeMap::new();
+        meta.insert("origin".to_string(), "kyc".to_string());
+        let token_args = runtime_args! {
+            "name" => token_name,
+            "contract_name" => token_name,
+            "symbol" => "symbol",
+            "meta" => meta,
+            "admin" => Key::Account(deployer)
+        };
+        let token_session = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(PathBuf::from("kyc-contract.wasm"), token_args)
+            .with_address(deployer)
+            .with_authorization_keys(&[deployer])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(token_session).build();
+        self.builder.exec(execute_request).expect_success().commit();
+        let named_value = |name: String| {
+            self.builder
+                .query(None, Key::Account(deployer), &[name])
+                .expect("should be stored value.")
+                .as_cl_value()
+                .expect("should be cl value.")
+                .clone()
+        };
+        (
+            named_value(format!("{}_package_hash_wrapped", token_name))
+                .into_t()
+                .expect("should be package hash."),
+            named_value(format!("{}_contract_hash_wrapped", token_name))
+                .into_t()
+                .expect("should be contract hash."),
+        )
+    }
+
+    /// Mint a KYC token of the token contract `token_hash` to `recipient`.
+    pub fn mint_kyc_token(
+        &mut self,
+        deployer: AccountHash,
+        token_hash: ContractHash,
+        recipient: AccountHash,
+    ) {
         let mut rng = rand::thread_rng();
         let mut token_meta = BTreeMap::new();
         token_meta.insert("status".to_string(), "active".to_string());
-        let args = runtime_args! {
+        let mint_args = runtime_args! {
             "recipient" => Key::Account(recipient),
             "token_id" => Some(recipient.to_string()),
             "token_meta" => token_meta
         };
         let deploy = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_stored_versioned_contract_by_hash(kyc_hash, None, "mint", args)
+            .with_stored_contract_by_hash(token_hash.value(), "mint", mint_args)
             .with_address(deployer)
             .with_authorization_keys(&[deployer])
             .with_deploy_hash(rng.gen())
             .build();
         let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
-        self.builder.exec(execute_request).commit();
+        self.builder.exec(execute_request).expect_success().commit();
+    }
+
+    /// Approve `recipient` without expiry on the provider installed under `kyc_hash`.
+    pub fn add_kyc(&mut self, deployer: AccountHash, kyc_hash: [u8; 32], recipient: AccountHash) {
+        self.call_provider(
+            deployer,
+            kyc_hash,
+            ProviderCall::approve(Key::Account(recipient), None, None),
+        );
+    }
+
+    /// Install a mock provider from the admin account, answering `false` until scripted otherwise.
+    pub fn deploy_mock_provider(&mut self, mock_name: &str) -> (ContractPackageHash, ContractHash) {
+        let mut rng = rand::thread_rng();
+        let code = PathBuf::from("mock_provider.wasm");
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(code, ProviderInstall::new(mock_name).args())
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).expect_success().commit();
+        let named_value = |name: String| {
+            self.builder
+                .query(None, Key::Account(self.admin_account.1), &[name])
+                .expect("should be stored value.")
+                .as_cl_value()
+                .expect("should be cl value.")
+                .clone()
+        };
+        (
+            named_value(names::provider::package_hash_key(mock_name))
+                .into_t()
+                .expect("should be package hash."),
+            named_value(names::provider::contract_hash_key(mock_name))
+                .into_t()
+                .expect("should be contract hash."),
+        )
+    }
+
+    /// Install a consumer contract from the admin account, which checks accounts through the proxy.
+    pub fn deploy_consumer(&mut self, consumer_name: &str) -> (ContractPackageHash, ContractHash) {
+        let mut rng = rand::thread_rng();
+        let code = PathBuf::from("kyc_consumer.wasm");
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(
+                code,
+                runtime_args! { names::consumer::args::CONSUMER_NAME => consumer_name },
+            )
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).expect_success().commit();
+        let named_value = |name: String| {
+            self.builder
+                .query(None, Key::Account(self.admin_account.1), &[name])
+                .expect("should be stored value.")
+                .as_cl_value()
+                .expect("should be cl value.")
+                .clone()
+        };
+        (
+            named_value(names::consumer::package_hash_key(consumer_name))
+                .into_t()
+                .expect("should be package hash."),
+            named_value(names::consumer::contract_hash_key(consumer_name))
+                .into_t()
+                .expect("should be contract hash."),
+        )
+    }
+
+    /// Have the consumer contract `consumer_hash` check `account` through the proxy, reverting with
+    /// `User(600)` if the answer differs from `result`.
+    pub fn check_through_consumer(
+        &mut self,
+        consumer_hash: ContractHash,
+        account: AccountHash,
+        result: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let mut rng = rand::thread_rng();
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_stored_contract_by_hash(
+                consumer_hash.value(),
+                names::consumer::entry_points::CHECK,
+                runtime_args! {
+                    names::consumer::args::KYC_PROXY_PACKAGE_HASH => self.package_hash,
+                    names::consumer::args::ACCOUNT => Key::Account(account),
+                    names::consumer::args::RESULT => result
+                },
+            )
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).commit()
+    }
+
+    /// Script the answer of the mock provider `mock_hash` for `account`.
+    pub fn set_mock_answer(
+        &mut self,
+        mock_hash: ContractHash,
+        account: AccountHash,
+        answer: MockAnswer,
+    ) {
+        self.call_mock_provider(
+            mock_hash,
+            MockProviderCall::set_answer(Key::Account(account), answer),
+        );
+    }
+
+    /// Script the answer of the mock provider `mock_hash` for accounts without an answer of their own.
+    pub fn set_mock_default_answer(&mut self, mock_hash: ContractHash, answer: MockAnswer) {
+        self.call_mock_provider(mock_hash, MockProviderCall::set_default_answer(answer));
+    }
+
+    fn call_mock_provider(&mut self, mock_hash: ContractHash, call: MockProviderCall) {
+        let mut rng = rand::thread_rng();
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_stored_contract_by_hash(mock_hash.value(), call.entry_point(), call.into_args())
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).expect_success().commit();
+    }
+
+    /// Run a session calling an entry point of the provider contract `kyc_hash`.
+    pub fn call_provider(
+        &mut self,
+        caller: AccountHash,
+        kyc_hash: [u8; 32],
+        call: ProviderCall,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let mut rng = rand::thread_rng();
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_stored_contract_by_hash(kyc_hash, call.entry_point(), call.into_args())
+            .with_address(caller)
+            .with_authorization_keys(&[caller])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).commit()
     }
 
     /// Getter function for the balance of an account.
@@ -251,87 +503,519 @@ impl ProxyContract {
         )
     }
 
-    /// Function that handles the creation and running of sessions.
-    fn call(&mut self, caller: AccountHash, method: &str, args: RuntimeArgs) {
+    /// Install another proxy contract from the admin account, without initial providers.
+    pub fn deploy_proxy(&mut self, proxy_name: &str) -> ContractPackageHash {
         let mut rng = rand::thread_rng();
+        let code = PathBuf::from("kyc-proxy.wasm");
         let deploy = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_stored_versioned_contract_by_hash(self.contract_hash, None, method, args)
-            .with_address(caller)
-            .with_authorization_keys(&[caller])
+            .with_session_code(code, ProxyInstall::new(proxy_name).args())
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
             .with_deploy_hash(rng.gen())
             .build();
         let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
-        self.builder.exec(execute_request).commit();
+        self.builder.exec(execute_request).expect_success().commit();
+        self.builder
+            .query(
+                None,
+                Key::Account(self.admin_account.1),
+                &[
+                    names::contract_key(proxy_name),
+                    names::contract_package_key(proxy_name),
+                ],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be string.")
+    }
+
+    /// Function that handles the creation and running of sessions.
+    fn call(
+        &mut self,
+        caller: AccountHash,
+        call: ProxyCall,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.call_proxy(self.package_hash, caller, call)
+    }
+
+    /// Run a session calling an entry point of the proxy installed under `proxy_package_hash`.
+    fn call_proxy(
+        &mut self,
+        proxy_package_hash: ContractPackageHash,
+        caller: AccountHash,
+        call: ProxyCall,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let mut rng = rand::thread_rng();
+        let deploy = deploy_item(
+            call.session(proxy_package_hash),
+            caller,
+            *DEFAULT_PAYMENT,
+            rng.gen(),
+        );
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).commit()
     }
 
     pub fn add_kyc_provider(&mut self, provider_package_hash_key: ContractPackageHash) {
         self.call(
             self.admin_account.1,
-            "add_kyc_provider",
-            runtime_args! {"provider"=>Key::Hash(provider_package_hash_key.value())},
+            ProxyCall::add_kyc_provider(provider_package_hash_key),
         );
     }
 
-    pub fn ban_provider(&mut self, provider_package_hash_key: ContractPackageHash) {
+    /// Register a provider with the optional arguments of `add_kyc_provider`.
+    pub fn add_kyc_provider_with(
+        &mut self,
+        add_kyc_provider: AddKycProvider,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.call(self.admin_account.1, add_kyc_provider.into())
+    }
+
+    /// Register a provider with the proxy installed under `proxy_package_hash`,
+    /// marking it as a nested proxy if `is_proxy` is set.
+    pub fn add_kyc_provider_to(
+        &mut self,
+        proxy_package_hash: ContractPackageHash,
+        provider_package_hash_key: ContractPackageHash,
+        is_proxy: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.call_proxy(
+            proxy_package_hash,
+            self.admin_account.1,
+            AddKycProvider::new(provider_package_hash_key)
+                .as_proxy(is_proxy)
+                .into(),
+        )
+    }
+
+    pub fn set_max_proxy_depth(&mut self, max_depth: u8) {
+        self.call(
+            self.admin_account.1,
+            ProxyCall::set_max_proxy_depth(max_depth),
+        )
+        .expect_success();
+    }
+
+    pub fn add_kyc_provider_with_metadata(
+        &mut self,
+        provider_package_hash_key: ContractPackageHash,
+        name: &str,
+        website: &str,
+        contact: &str,
+        description: &str,
+    ) {
+        let metadata = ProviderMetadataFields {
+            name: Some(name.to_string()),
+            website: Some(website.to_string()),
+            contact: Some(contact.to_string()),
+            description: Some(description.to_string()),
+        };
         self.call(
             self.admin_account.1,
-            "ban_provider",
-            runtime_args! {"provider"=>Key::Hash(provider_package_hash_key.value())},
+            AddKycProvider::new(provider_package_hash_key)
+                .with_metadata(metadata)
+                .into(),
         );
     }
 
-    pub fn unban_provider(&mut self, provider_package_hash_key: ContractPackageHash) {
+    /// Update the metadata of a provider, overwriting the fields that are set in `metadata`.
+    pub fn update_provider_metadata(
+        &mut self,
+        provider_package_hash_key: ContractPackageHash,
+        metadata: ProviderMetadataFields,
+    ) {
         self.call(
             self.admin_account.1,
-            "unban_provider",
-            runtime_args! {"provider"=>Key::Hash(provider_package_hash_key.value())},
+            ProxyCall::update_provider_metadata(provider_package_hash_key, metadata),
         );
     }
 
-    pub fn is_kyc_proved(&mut self, result: bool) -> &mut WasmTestBuilder<InMemoryGlobalState> {
-        let mut rng = rand::thread_rng();
-        let code = PathBuf::from("test_contract.wasm");
-        let deploy = DeployItemBuilder::new()
-            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_session_code(
-                code,
-                runtime_args! {
-                    "kyc_proxy_package_hash"=>self.package_hash,
-                    "result" => result
-                },
+    /// Read the metadata record of a provider straight from the proxy's "kyc_provider_metadata" dictionary.
+    pub fn get_provider_info(
+        &self,
+        provider_package_hash_key: ContractPackageHash,
+    ) -> Option<ProviderInfo> {
+        let dictionary_uref = self.contract_dictionary_uref(named_keys::KYC_PROVIDER_METADATA);
+        let stored_value = self
+            .builder
+            .query_dictionary_item(
+                None,
+                dictionary_uref,
+                &provider_package_hash_key.to_string(),
             )
-            .with_address(self.admin_account.1)
-            .with_authorization_keys(&[self.admin_account.1])
-            .with_deploy_hash(rng.gen())
-            .build();
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
-        self.builder.exec(execute_request).commit()
+            .ok()?;
+        let ((name, website, contact), (description, added_at, added_by)): (
+            (String, String, String),
+            (String, u64, Key),
+        ) = stored_value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be provider metadata.");
+        Some(ProviderInfo {
+            name,
+            website,
+            contact,
+            description,
+            added_at,
+            added_by,
+        })
     }
-}
 
-#[test]
-fn test_deploy() {
-    ProxyContract::deploy();
-}
+    /// Read the profile of a consumer contract straight from the proxy's "kyc_consumer_profiles" dictionary.
+    pub fn get_consumer_profile(
+        &self,
+        consumer_package_hash: ContractPackageHash,
+    ) -> Option<ConsumerProfile> {
+        let dictionary_uref = self.contract_dictionary_uref(named_keys::KYC_CONSUMER_PROFILES);
+        let stored_value = self
+            .builder
+            .query_dictionary_item(None, dictionary_uref, &consumer_package_hash.to_string())
+            .ok()?;
+        let bytes = stored_value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .inner_bytes()
+            .to_vec();
+        bytesrepr::deserialize::<Option<ConsumerProfile>>(bytes)
+            .expect("should be consumer profile.")
+    }
 
-#[test]
-fn test_no_provider() {
-    let mut proxy = ProxyContract::deploy();
-    proxy.is_kyc_proved(false);
-}
+    /// Read the number of checks a consumer contract called in the current billing period, straight from
+    /// the proxy's "kyc_contract_calls" dictionary.
+    pub fn get_contract_usage(&self, consumer_package_hash: ContractPackageHash) -> u64 {
+        self.usage_counter(
+            named_keys::KYC_CONTRACT_CALLS,
+            &consumer_package_hash.to_string(),
+        )
+    }
 
-#[test]
-#[should_panic = "User(999)"]
-fn test_no_provider_failing() {
-    let mut proxy = ProxyContract::deploy();
-    proxy.is_kyc_proved(true).expect_success();
-}
+    /// Read how often a provider was asked, and approved, in the current billing period, straight from
+    /// the proxy's "kyc_provider_calls" and "kyc_provider_approvals" dictionaries.
+    pub fn get_provider_usage(&self, provider_package_hash: ContractPackageHash) -> ProviderUsage {
+        let str_provider = provider_package_hash.to_string();
+        ProviderUsage {
+            calls: self.usage_counter(named_keys::KYC_PROVIDER_CALLS, &str_provider),
+            approvals: self.usage_counter(named_keys::KYC_PROVIDER_APPROVALS, &str_provider),
+        }
+    }
 
-#[test]
-fn test_single_provider_proxy_negative() {
-    let mut proxy = ProxyContract::deploy();
-    let (first_provider_package_hash, _first_provider_hash) =
+    /// Read a usage counter, which only counts if it was stored in the current billing period.
+    fn usage_counter(&self, dictionary: &str, item_key: &str) -> u64 {
+        let period: u32 = self
+            .builder
+            .get_contract(ContractHash::new(self.contract_hash))
+            .expect("should be contract.")
+            .named_keys()
+            .get(named_keys::USAGE_PERIOD)
+            .map(|period_key| {
+                self.builder
+                    .query(None, *period_key, &[])
+                    .expect("should be stored value.")
+                    .as_cl_value()
+                    .expect("should be cl value.")
+                    .clone()
+                    .into_t()
+                    .expect("should be u32.")
+            })
+            .unwrap_or_default();
+        let dictionary_uref = self.contract_dictionary_uref(dictionary);
+        match self
+            .builder
+            .query_dictionary_item(None, dictionary_uref, item_key)
+        {
+            Ok(stored_value) => {
+                let (counted_period, count): (u32, u64) = stored_value
+                    .as_cl_value()
+                    .expect("should be cl value.")
+                    .clone()
+                    .into_t()
+                    .expect("should be usage counter.");
+                if counted_period == period {
+                    count
+                } else {
+                    0
+                }
+            }
+            Err(_) => 0,
+        }
+    }
+
+    /// Return the seed URef of a dictionary stored under the proxy contract's named keys.
+    fn contract_dictionary_uref(&self, name: &str) -> URef {
+        *self
+            .builder
+            .get_contract(ContractHash::new(self.contract_hash))
+            .expect("should be contract.")
+            .named_keys()
+            .get(name)
+            .expect("should be named key.")
+            .as_uref()
+            .expect("should be uref.")
+    }
+
+    /// Read the raw stored values of the registry, keyed by their address.
+    pub fn raw_registry_values(&self) -> BTreeMap<Key, StoredValue> {
+        let mut stored_values = BTreeMap::new();
+        RegistrySnapshot::from_lookup(&self.registry_urefs(), |key| {
+            let stored_value = self.builder.query(None, *key, &[]).ok()?;
+            stored_values.insert(*key, stored_value.clone());
+            Some(stored_value)
+        })
+        .expect("should be registry.");
+        stored_values
+    }
+
+    pub fn registry_urefs(&self) -> RegistryUrefs {
+        RegistryUrefs {
+            index: self.contract_dictionary_uref(named_keys::KYC_PROVIDER_INDEX),
+            records: self.contract_dictionary_uref(named_keys::KYC_PROVIDER_RECORDS),
+            count: self.contract_dictionary_uref(named_keys::KYC_PROVIDER_COUNT),
+        }
+    }
+
+    pub fn registry_snapshot(&self) -> RegistrySnapshot {
+        RegistrySnapshot::from_stored_values(&self.registry_urefs(), &self.raw_registry_values())
+            .expect("should be registry.")
+    }
+
+    /// Return the storage schema version stored by the proxy.
+    pub fn schema_version(&self) -> u8 {
+        let uref = self.contract_dictionary_uref(named_keys::SCHEMA_VERSION);
+        self.builder
+            .query(None, Key::URef(uref), &[])
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be u8.")
+    }
+
+    pub fn migrate_storage(&mut self) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.call(self.admin_account.1, ProxyCall::migrate_storage())
+    }
+
+    pub fn set_provider_status(
+        &mut self,
+        provider_package_hash_key: ContractPackageHash,
+        status: ProviderStatus,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.call(
+            self.admin_account.1,
+            ProxyCall::set_provider_status(provider_package_hash_key, status),
+        )
+    }
+
+    pub fn ban_provider(&mut self, provider_package_hash_key: ContractPackageHash) {
+        self.call(
+            self.admin_account.1,
+            ProxyCall::ban_provider(provider_package_hash_key),
+        );
+    }
+
+    pub fn unban_provider(&mut self, provider_package_hash_key: ContractPackageHash) {
+        self.call(
+            self.admin_account.1,
+            ProxyCall::unban_provider(provider_package_hash_key),
+        );
+    }
+
+    pub fn set_provider_jurisdictions(
+        &mut self,
+        provider_package_hash_key: ContractPackageHash,
+        jurisdictions: Vec<&str>,
+    ) {
+        let jurisdictions: Vec<String> = jurisdictions.iter().map(|j| j.to_string()).collect();
+        self.call(
+            self.admin_account.1,
+            ProxyCall::set_provider_jurisdictions(provider_package_hash_key, jurisdictions),
+        );
+    }
+
+    pub fn is_kyc_proved(&mut self, result: bool) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::IS_KYC_PROVED,
+            "result" => result
+        })
+    }
+
+    pub fn is_kyc_proved_for_public_key(
+        &mut self,
+        public_key: PublicKey,
+        result: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::IS_KYC_PROVED_FOR_PUBLIC_KEY,
+            args::PUBLIC_KEY => public_key,
+            "result" => result
+        })
+    }
+
+    pub fn am_i_kyc_proved(&mut self, result: bool) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::AM_I_KYC_PROVED,
+            "result" => result
+        })
+    }
+
+    pub fn is_kyc_proved_in(
+        &mut self,
+        jurisdictions: Vec<&str>,
+        result: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let jurisdictions: Vec<String> = jurisdictions.iter().map(|j| j.to_string()).collect();
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::IS_KYC_PROVED_IN,
+            args::JURISDICTIONS => jurisdictions,
+            "result" => result
+        })
+    }
+
+    pub fn is_kyc_proved_with(
+        &mut self,
+        providers: Vec<ContractPackageHash>,
+        result: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::IS_KYC_PROVED_WITH,
+            args::PROVIDERS => providers,
+            "result" => result
+        })
+    }
+
+    pub fn are_kyc_proved(
+        &mut self,
+        accounts: Vec<AccountHash>,
+        results: Vec<bool>,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let accounts: Vec<Key> = accounts.into_iter().map(Key::Account).collect();
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::ARE_KYC_PROVED,
+            args::ACCOUNTS => accounts,
+            "result" => results
+        })
+    }
+
+    pub fn all_kyc_proved(
+        &mut self,
+        accounts: Vec<AccountHash>,
+        result: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let accounts: Vec<Key> = accounts.into_iter().map(Key::Account).collect();
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::ALL_KYC_PROVED,
+            args::ACCOUNTS => accounts,
+            "result" => result
+        })
+    }
+
+    pub fn any_kyc_proved(
+        &mut self,
+        accounts: Vec<AccountHash>,
+        result: bool,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let accounts: Vec<Key> = accounts.into_iter().map(Key::Account).collect();
+        self.check_through_session(runtime_args! {
+            "entry_point" => entry_points::ANY_KYC_PROVED,
+            args::ACCOUNTS => accounts,
+            "result" => result
+        })
+    }
+
+    /// Run the `record_kyc_verdict` session code from `caller` at the given block time, checking `account`
+    /// (the caller itself if `None`).
+    pub fn record_kyc_verdict(
+        &mut self,
+        caller: AccountHash,
+        account: Option<AccountHash>,
+        block_time: u64,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let mut rng = rand::thread_rng();
+        let code = PathBuf::from("record_kyc_verdict.wasm");
+        let mut session = VerdictSession::new(self.package_hash);
+        if let Some(account) = account {
+            session = session.with_account(Key::Account(account));
+        }
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(code, session.args())
+            .with_address(caller)
+            .with_authorization_keys(&[caller])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_block_time(block_time)
+            .build();
+        self.builder.exec(execute_request).commit()
+    }
+
+    /// Read the `(verdict, block time)` stored by `record_kyc_verdict` in an account's named keys.
+    pub fn get_kyc_verdict(&self, account: AccountHash) -> (bool, u64) {
+        self.builder
+            .query(
+                None,
+                Key::Account(account),
+                &[named_keys::KYC_VERDICT.to_string()],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be verdict.")
+    }
+
+    /// Run the test session code, which calls a checking entry point of the proxy for the admin account
+    /// (or the given "accounts") and reverts with `User(999)` if the answer differs from the expected `result`.
+    fn check_through_session(
+        &mut self,
+        mut session_args: RuntimeArgs,
+    ) -> &mut WasmTestBuilder<InMemoryGlobalState> {
+        let mut rng = rand::thread_rng();
+        let code = PathBuf::from("test_contract.wasm");
+        session_args
+            .insert(args::KYC_PROXY_PACKAGE_HASH, self.package_hash)
+            .unwrap();
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(code, session_args)
+            .with_address(self.admin_account.1)
+            .with_authorization_keys(&[self.admin_account.1])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        self.builder.exec(execute_request).commit()
+    }
+}
+
+#[test]
+fn test_deploy() {
+    ProxyContract::deploy();
+}
+
+#[test]
+fn test_no_provider() {
+    let mut proxy = ProxyContract::deploy();
+    proxy.is_kyc_proved(false);
+}
+
+#[test]
+#[should_panic = "User(999)"]
+fn test_no_provider_failing() {
+    let mut proxy = ProxyContract::deploy();
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_single_provider_proxy_negative() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _first_provider_hash) =
         proxy.deploy_kyc(proxy.participant_two.1, "first");
     proxy.add_kyc_provider(first_provider_package_hash);
     proxy.is_kyc_proved(false);
@@ -432,3 +1116,1685 @@ fn test_unbanned_provider() {
     proxy.unban_provider(first_provider_package_hash);
     proxy.is_kyc_proved(true);
 }
+
+#[test]
+fn test_jurisdiction_provider_positive() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.set_provider_jurisdictions(first_provider_package_hash, vec!["EU", "ch"]);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved_in(vec!["EU"], true).expect_success();
+    proxy
+        .is_kyc_proved_in(vec!["US", "CH"], true)
+        .expect_success();
+}
+
+#[test]
+fn test_jurisdiction_provider_negative() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.set_provider_jurisdictions(first_provider_package_hash, vec!["EU"]);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved_in(vec!["US"], false).expect_success();
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_jurisdiction_untagged_provider_is_skipped() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved_in(vec!["EU"], false).expect_success();
+}
+
+#[test]
+fn test_jurisdiction_banned_provider() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.set_provider_jurisdictions(first_provider_package_hash, vec!["EU"]);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.ban_provider(first_provider_package_hash);
+    proxy.is_kyc_proved_in(vec!["EU"], false).expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_provider_jurisdictions_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_provider_jurisdictions(
+                first_provider_package_hash,
+                vec!["EU".to_string()],
+            ),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_provider_metadata_on_add() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider_with_metadata(
+        first_provider_package_hash,
+        "First KYC",
+        "https://first.example",
+        "ops@first.example",
+        "EU certified provider",
+    );
+    let info = proxy
+        .get_provider_info(first_provider_package_hash)
+        .expect("should have metadata.");
+    assert_eq!(info.name, "First KYC");
+    assert_eq!(info.website, "https://first.example");
+    assert_eq!(info.contact, "ops@first.example");
+    assert_eq!(info.description, "EU certified provider");
+    assert_eq!(info.added_by, Key::Account(proxy.admin_account.1));
+}
+
+#[test]
+fn test_provider_metadata_defaults() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    let info = proxy
+        .get_provider_info(first_provider_package_hash)
+        .expect("should have metadata.");
+    assert_eq!(info.name, "");
+    assert_eq!(info.added_by, Key::Account(proxy.admin_account.1));
+}
+
+#[test]
+fn test_provider_metadata_update() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider_with_metadata(
+        first_provider_package_hash,
+        "First KYC",
+        "https://first.example",
+        "ops@first.example",
+        "EU certified provider",
+    );
+    proxy.update_provider_metadata(
+        first_provider_package_hash,
+        ProviderMetadataFields {
+            contact: Some("compliance@first.example".to_string()),
+            ..Default::default()
+        },
+    );
+    let info = proxy
+        .get_provider_info(first_provider_package_hash)
+        .expect("should have metadata.");
+    assert_eq!(info.name, "First KYC");
+    assert_eq!(info.contact, "compliance@first.example");
+}
+
+#[test]
+fn test_provider_metadata_unknown_provider() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.update_provider_metadata(
+        first_provider_package_hash,
+        ProviderMetadataFields {
+            name: Some("First KYC".to_string()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(proxy.get_provider_info(first_provider_package_hash), None);
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_provider_metadata_update_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::update_provider_metadata(
+                first_provider_package_hash,
+                ProviderMetadataFields {
+                    website: Some("https://example.com".to_string()),
+                    ..Default::default()
+                },
+            ),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_nested_proxy_positive() {
+    let mut proxy = ProxyContract::deploy();
+    let regional_proxy_package_hash = proxy.deploy_proxy("regional");
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy
+        .add_kyc_provider_to(
+            regional_proxy_package_hash,
+            first_provider_package_hash,
+            false,
+        )
+        .expect_success();
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .add_kyc_provider_to(proxy.package_hash, regional_proxy_package_hash, true)
+        .expect_success();
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_nested_proxy_negative() {
+    let mut proxy = ProxyContract::deploy();
+    let regional_proxy_package_hash = proxy.deploy_proxy("regional");
+    let (first_provider_package_hash, _first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy
+        .add_kyc_provider_to(
+            regional_proxy_package_hash,
+            first_provider_package_hash,
+            false,
+        )
+        .expect_success();
+    proxy
+        .add_kyc_provider_to(proxy.package_hash, regional_proxy_package_hash, true)
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+}
+
+#[test]
+fn test_nested_proxy_cycle() {
+    let mut proxy = ProxyContract::deploy();
+    let regional_proxy_package_hash = proxy.deploy_proxy("regional");
+    proxy
+        .add_kyc_provider_to(proxy.package_hash, regional_proxy_package_hash, true)
+        .expect_success();
+    proxy
+        .add_kyc_provider_to(regional_proxy_package_hash, proxy.package_hash, true)
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+}
+
+#[test]
+fn test_nested_proxy_cycle_positive() {
+    let mut proxy = ProxyContract::deploy();
+    let regional_proxy_package_hash = proxy.deploy_proxy("regional");
+    proxy
+        .add_kyc_provider_to(proxy.package_hash, regional_proxy_package_hash, true)
+        .expect_success();
+    proxy
+        .add_kyc_provider_to(regional_proxy_package_hash, proxy.package_hash, true)
+        .expect_success();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy
+        .add_kyc_provider_to(
+            regional_proxy_package_hash,
+            first_provider_package_hash,
+            false,
+        )
+        .expect_success();
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_nested_proxy_max_depth() {
+    let mut proxy = ProxyContract::deploy();
+    let regional_proxy_package_hash = proxy.deploy_proxy("regional");
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy
+        .add_kyc_provider_to(
+            regional_proxy_package_hash,
+            first_provider_package_hash,
+            false,
+        )
+        .expect_success();
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .add_kyc_provider_to(proxy.package_hash, regional_proxy_package_hash, true)
+        .expect_success();
+    proxy.set_max_proxy_depth(0);
+    proxy.is_kyc_proved(false).expect_success();
+    // only admins may change the depth back
+    proxy
+        .call(proxy.participant_two.1, ProxyCall::set_max_proxy_depth(4))
+        .expect_failure();
+    proxy.is_kyc_proved(false).expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_provider_is_proxy_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_provider_is_proxy(first_provider_package_hash, true),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(301)"]
+fn test_proxy_self_reference() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .add_kyc_provider_to(proxy.package_hash, proxy.package_hash, true)
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(301)"]
+fn test_proxy_self_reference_by_contract_hash() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(ContractPackageHash::new(proxy.contract_hash))
+                .with_addressing(ProviderAddressing::Contract),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_batch_mixed_accounts() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    let accounts = vec![proxy.admin_account.1, proxy.participant_three.1];
+    proxy
+        .are_kyc_proved(accounts.clone(), vec![true, false])
+        .expect_success();
+    proxy
+        .all_kyc_proved(accounts.clone(), false)
+        .expect_success();
+    proxy.any_kyc_proved(accounts, true).expect_success();
+}
+
+#[test]
+fn test_batch_multiple_providers() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, second_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_three.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.add_kyc(
+        proxy.participant_three.1,
+        second_provider_hash.value(),
+        proxy.participant_two.1,
+    );
+    let accounts = vec![
+        proxy.admin_account.1,
+        proxy.participant_two.1,
+        proxy.participant_three.1,
+    ];
+    proxy
+        .are_kyc_proved(accounts, vec![true, true, false])
+        .expect_success();
+    proxy
+        .all_kyc_proved(vec![proxy.admin_account.1, proxy.participant_two.1], true)
+        .expect_success();
+}
+
+#[test]
+fn test_batch_banned_provider() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.ban_provider(first_provider_package_hash);
+    let accounts = vec![proxy.admin_account.1, proxy.participant_three.1];
+    proxy
+        .are_kyc_proved(accounts.clone(), vec![false, false])
+        .expect_success();
+    proxy.any_kyc_proved(accounts, false).expect_success();
+}
+
+#[test]
+fn test_batch_no_accounts() {
+    let mut proxy = ProxyContract::deploy();
+    proxy.are_kyc_proved(vec![], vec![]).expect_success();
+    proxy.all_kyc_proved(vec![], true).expect_success();
+    proxy.any_kyc_proved(vec![], false).expect_success();
+}
+
+#[test]
+fn test_record_verdict_for_caller() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .record_kyc_verdict(proxy.admin_account.1, None, 1_000)
+        .expect_success();
+    assert_eq!(proxy.get_kyc_verdict(proxy.admin_account.1), (true, 1_000));
+}
+
+#[test]
+fn test_record_verdict_for_other_account() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .record_kyc_verdict(
+            proxy.participant_three.1,
+            Some(proxy.admin_account.1),
+            1_000,
+        )
+        .expect_success();
+    assert_eq!(
+        proxy.get_kyc_verdict(proxy.participant_three.1),
+        (true, 1_000)
+    );
+    proxy
+        .record_kyc_verdict(
+            proxy.admin_account.1,
+            Some(proxy.participant_three.1),
+            1_000,
+        )
+        .expect_success();
+    assert_eq!(proxy.get_kyc_verdict(proxy.admin_account.1), (false, 1_000));
+}
+
+#[test]
+fn test_record_verdict_overwrite() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .record_kyc_verdict(proxy.admin_account.1, None, 1_000)
+        .expect_success();
+    proxy.ban_provider(first_provider_package_hash);
+    proxy
+        .record_kyc_verdict(proxy.admin_account.1, None, 2_000)
+        .expect_success();
+    assert_eq!(proxy.get_kyc_verdict(proxy.admin_account.1), (false, 2_000));
+}
+
+#[test]
+fn test_registry_snapshot_empty() {
+    let proxy = ProxyContract::deploy();
+    assert_eq!(proxy.registry_snapshot(), RegistrySnapshot::default());
+}
+
+#[test]
+fn test_registry_snapshot_after_ban() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.ban_provider(first_provider_package_hash);
+    let snapshot = proxy.registry_snapshot();
+    assert_eq!(
+        snapshot.providers,
+        vec![
+            RegistryEntry {
+                index: 0,
+                package_hash: first_provider_package_hash,
+                status: ProviderStatus::Banned {
+                    reason: String::new()
+                },
+            },
+            RegistryEntry {
+                index: 1,
+                package_hash: second_provider_package_hash,
+                status: ProviderStatus::Active,
+            },
+        ]
+    );
+    assert_eq!(
+        snapshot.active_providers(),
+        vec![second_provider_package_hash]
+    );
+    proxy.unban_provider(first_provider_package_hash);
+    assert_eq!(
+        proxy.registry_snapshot().active_providers(),
+        vec![first_provider_package_hash, second_provider_package_hash]
+    );
+}
+
+#[test]
+fn test_provider_status() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    let suspended = ProviderStatus::Suspended {
+        until: u64::MAX,
+        reason: "audit".to_string(),
+    };
+    proxy
+        .set_provider_status(first_provider_package_hash, suspended.clone())
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+    assert_eq!(proxy.registry_snapshot().providers[0].status, suspended);
+
+    proxy
+        .set_provider_status(first_provider_package_hash, ProviderStatus::Deprecated)
+        .expect_success();
+    proxy.is_kyc_proved(true).expect_success();
+
+    proxy.call(
+        proxy.admin_account.1,
+        ProxyCall::ban_provider_for(first_provider_package_hash, "fraud".to_string()),
+    );
+    proxy.is_kyc_proved(false).expect_success();
+    assert_eq!(
+        proxy.registry_snapshot().providers[0].status,
+        ProviderStatus::Banned {
+            reason: "fraud".to_string()
+        }
+    );
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_provider_status_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_provider_status(first_provider_package_hash, ProviderStatus::Deprecated),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_add_registered_provider_keeps_settings() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(first_provider_package_hash)
+                .with_adapter(ProviderAdapter::Cep47)
+                .with_metadata(ProviderMetadataFields {
+                    name: Some("First KYC".to_string()),
+                    ..Default::default()
+                }),
+        )
+        .expect_success();
+    proxy.is_kyc_proved(true).expect_success();
+    let info = proxy
+        .get_provider_info(first_provider_package_hash)
+        .expect("should have metadata.");
+    assert_eq!(info.name, "");
+}
+
+#[test]
+fn test_cep47_adapter() {
+    let mut proxy = ProxyContract::deploy();
+    let (token_package_hash, token_hash) = proxy.deploy_kyc_token(proxy.participant_two.1, "token");
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(token_package_hash).with_adapter(ProviderAdapter::Cep47),
+        )
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+    proxy.mint_kyc_token(proxy.participant_two.1, token_hash, proxy.admin_account.1);
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_cep78_adapter() {
+    let mut proxy = ProxyContract::deploy();
+    let (token_package_hash, token_hash) = proxy.deploy_mock_provider("token");
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(token_package_hash).with_adapter(ProviderAdapter::Cep78),
+        )
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+    proxy.set_mock_answer(token_hash, proxy.admin_account.1, MockAnswer::True);
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_custom_call_spec() {
+    let mut proxy = ProxyContract::deploy();
+    let (token_package_hash, token_hash) = proxy.deploy_kyc_token(proxy.participant_two.1, "token");
+    let adapter = ProviderAdapter::Custom(CallSpec {
+        entry_point: "balance_of".to_string(),
+        account_arg: "owner".to_string(),
+        index_arg: None,
+        returns: ReturnKind::PositiveU256,
+    });
+    proxy
+        .add_kyc_provider_with(AddKycProvider::new(token_package_hash).with_adapter(adapter))
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+    proxy.mint_kyc_token(proxy.participant_two.1, token_hash, proxy.admin_account.1);
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_registry_raw_values_addresses() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    let urefs = proxy.registry_urefs();
+    let stored_values = proxy.raw_registry_values();
+    let addresses: Vec<Key> = stored_values.keys().copied().collect();
+    let mut expected = vec![
+        registry::count_address(&urefs),
+        registry::index_address(&urefs, 0),
+        registry::record_address(&urefs, first_provider_package_hash),
+    ];
+    expected.sort();
+    assert_eq!(addresses, expected);
+}
+
+#[test]
+fn test_registry_missing_value() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    let urefs = proxy.registry_urefs();
+    let mut stored_values = proxy.raw_registry_values();
+    let record_address = registry::record_address(&urefs, first_provider_package_hash);
+    stored_values.remove(&record_address);
+    match RegistrySnapshot::from_stored_values(&urefs, &stored_values) {
+        Err(DecodeError::Missing(key)) => assert_eq!(key, record_address),
+        other => panic!("unexpected decoding result {:?}", other),
+    }
+}
+
+#[test]
+fn test_migrate_storage_keeps_current_registry() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    let snapshot = proxy.registry_snapshot();
+    assert_eq!(proxy.schema_version(), 2);
+    proxy.migrate_storage().expect_success();
+    assert_eq!(proxy.schema_version(), 2);
+    assert_eq!(proxy.registry_snapshot(), snapshot);
+}
+
+#[test]
+fn test_upgrade_v1_proxy() {
+    let mut proxy = ProxyContract::deploy_v1();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, second_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_three.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.ban_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved(false).expect_success();
+
+    let package_hash = proxy.package_hash;
+    proxy.upgrade();
+    assert_eq!(proxy.package_hash, package_hash);
+    assert_eq!(proxy.schema_version(), 2);
+    assert_eq!(
+        proxy.registry_snapshot().active_providers(),
+        vec![second_provider_package_hash]
+    );
+    let info = proxy
+        .get_provider_info(second_provider_package_hash)
+        .expect("migrated provider should have metadata.");
+    assert_eq!(info.added_by, Key::Account(proxy.admin_account.1));
+    assert_eq!(info.name, "");
+    proxy.is_kyc_proved(false).expect_success();
+    proxy.add_kyc(
+        proxy.participant_three.1,
+        second_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved(true).expect_success();
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::set_restricted_mode(false))
+        .expect_success();
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::unban_provider(first_provider_package_hash),
+        )
+        .expect_success();
+    assert_eq!(
+        proxy.registry_snapshot().active_providers(),
+        vec![first_provider_package_hash, second_provider_package_hash]
+    );
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_upgraded_v1_proxy_rejects_non_admin() {
+    let mut proxy = ProxyContract::deploy_v1();
+    proxy.upgrade();
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_restricted_mode(true),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_provider_revoke() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved(true).expect_success();
+    proxy
+        .call_provider(
+            proxy.participant_two.1,
+            first_provider_hash.value(),
+            ProviderCall::revoke(Key::Account(proxy.admin_account.1), None),
+        )
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+}
+
+#[test]
+fn test_linked_account_follows_primary() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    let (primary, secondary) = (proxy.admin_account.1, proxy.participant_two.1);
+    proxy
+        .call(primary, ProxyCall::propose_link(secondary))
+        .expect_success();
+    proxy
+        .are_kyc_proved(vec![secondary], vec![false])
+        .expect_success();
+    proxy
+        .call(secondary, ProxyCall::accept_link(primary))
+        .expect_success();
+    proxy
+        .are_kyc_proved(vec![secondary], vec![true])
+        .expect_success();
+
+    proxy
+        .call_provider(
+            proxy.participant_two.1,
+            first_provider_hash.value(),
+            ProviderCall::revoke(Key::Account(primary), None),
+        )
+        .expect_success();
+    proxy
+        .are_kyc_proved(vec![secondary], vec![false])
+        .expect_success();
+
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        primary,
+    );
+    proxy
+        .call(secondary, ProxyCall::unlink(primary))
+        .expect_success();
+    proxy
+        .are_kyc_proved(vec![secondary], vec![false])
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(307)"]
+fn test_unproved_primary_can_not_propose_link() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::propose_link(proxy.participant_two.1),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_max_links_per_primary_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_max_links_per_primary(1),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(317)"]
+fn test_propose_link_to_non_account() {
+    let mut proxy = ProxyContract::deploy();
+    let mut rng = rand::thread_rng();
+    let deploy = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+        .with_stored_contract_by_hash(
+            proxy.contract_hash,
+            entry_points::PROPOSE_LINK,
+            runtime_args! { args::SECONDARY => Key::Hash([7u8; 32]) },
+        )
+        .with_address(proxy.admin_account.1)
+        .with_authorization_keys(&[proxy.admin_account.1])
+        .with_deploy_hash(rng.gen())
+        .build();
+    let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+    proxy.builder.exec(execute_request).expect_success();
+}
+
+/// Install a proxy with a provider approving the admin account, in privacy mode.
+fn private_proxy() -> ProxyContract {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::set_privacy_mode(true))
+        .expect_success();
+    proxy
+}
+
+#[test]
+fn test_privacy_mode_allows_own_checks() {
+    let mut proxy = private_proxy();
+    proxy.am_i_kyc_proved(true).expect_success();
+    // the session checks the account sending the deploy
+    proxy.is_kyc_proved(true).expect_success();
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::set_privacy_mode(false))
+        .expect_success();
+    proxy
+        .are_kyc_proved(vec![proxy.participant_two.1], vec![false])
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(310)"]
+fn test_privacy_mode_denies_third_party_checks() {
+    let mut proxy = private_proxy();
+    proxy
+        .are_kyc_proved(vec![proxy.participant_two.1], vec![false])
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_third_party_access_by_non_admin() {
+    let mut proxy = private_proxy();
+    let (consumer_package_hash, _) = proxy.deploy_consumer("consumer");
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_third_party_access(consumer_package_hash, true),
+        )
+        .expect_success();
+}
+
+/// A proxy in restricted mode, whose first provider approves the admin account.
+fn restricted_proxy() -> ProxyContract {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::set_restricted_mode(true))
+        .expect_success();
+    proxy
+}
+
+#[test]
+#[should_panic = "User(312)"]
+fn test_restricted_mode_rejects_unknown_session() {
+    let mut proxy = restricted_proxy();
+    proxy.am_i_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_restricted_mode_allows_registered_session() {
+    let mut proxy = restricted_proxy();
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::add_consumer(Key::Account(proxy.admin_account.1)),
+        )
+        .expect_success();
+    proxy.is_kyc_proved(true).expect_success();
+    proxy.am_i_kyc_proved(true).expect_success();
+}
+
+#[test]
+#[should_panic = "User(312)"]
+fn test_restricted_mode_rejects_unknown_contract() {
+    let mut proxy = restricted_proxy();
+    let (_, consumer_hash) = proxy.deploy_consumer("consumer");
+    // registering the deploy's account does not register the contract it calls
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::add_consumer(Key::Account(proxy.admin_account.1)),
+        )
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+}
+
+#[test]
+fn test_restricted_mode_allows_registered_contract() {
+    let mut proxy = restricted_proxy();
+    let (consumer_package_hash, consumer_hash) = proxy.deploy_consumer("consumer");
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::add_consumer(Key::Hash(consumer_package_hash.value())),
+        )
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.participant_two.1, false)
+        .expect_success();
+
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::remove_consumer(Key::Hash(consumer_package_hash.value())),
+        )
+        .expect_success();
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::set_restricted_mode(false))
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_add_consumer_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::add_consumer(Key::Account(proxy.participant_two.1)),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_set_restricted_mode_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_restricted_mode(true),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_removed_admin_is_rejected() {
+    let mut proxy = restricted_proxy();
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::add_admin(proxy.participant_two.1),
+        )
+        .expect_success();
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_restricted_mode(false),
+        )
+        .expect_success();
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::remove_admin(proxy.participant_two.1),
+        )
+        .expect_success();
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_restricted_mode(true),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_consumer_profile_restricts_providers() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    let (consumer_package_hash, consumer_hash) = proxy.deploy_consumer("consumer");
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+
+    let profile = ConsumerProfile {
+        providers: Some(vec![second_provider_package_hash]),
+        ..ConsumerProfile::default()
+    };
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::set_consumer_profile(consumer_package_hash, profile.clone()),
+        )
+        .expect_success();
+    assert_eq!(
+        proxy.get_consumer_profile(consumer_package_hash),
+        Some(profile)
+    );
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, false)
+        .expect_success();
+    // checks from session code keep the global configuration
+    proxy.is_kyc_proved(true).expect_success();
+
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::remove_consumer_profile(consumer_package_hash),
+        )
+        .expect_success();
+    assert_eq!(proxy.get_consumer_profile(consumer_package_hash), None);
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+}
+
+#[test]
+fn test_consumer_profile_threshold_and_tier() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, second_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    let (consumer_package_hash, consumer_hash) = proxy.deploy_consumer("consumer");
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::set_consumer_profile(
+                consumer_package_hash,
+                ConsumerProfile {
+                    threshold: 2,
+                    ..ConsumerProfile::default()
+                },
+            ),
+        )
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, false)
+        .expect_success();
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        second_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::set_consumer_profile(
+                consumer_package_hash,
+                ConsumerProfile {
+                    min_tier: 1,
+                    ..ConsumerProfile::default()
+                },
+            ),
+        )
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, false)
+        .expect_success();
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::set_provider_tier(second_provider_package_hash, 1),
+        )
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(313)"]
+fn test_consumer_profile_rejects_zero_threshold() {
+    let mut proxy = ProxyContract::deploy();
+    let (consumer_package_hash, _) = proxy.deploy_consumer("consumer");
+    proxy
+        .call(
+            proxy.admin_account.1,
+            ProxyCall::set_consumer_profile(
+                consumer_package_hash,
+                ConsumerProfile {
+                    threshold: 0,
+                    ..ConsumerProfile::default()
+                },
+            ),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_consumer_profile_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (consumer_package_hash, _) = proxy.deploy_consumer("consumer");
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_consumer_profile(consumer_package_hash, ConsumerProfile::default()),
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_provider_tier_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call(
+            proxy.participant_two.1,
+            ProxyCall::set_provider_tier(first_provider_package_hash, 1),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_is_kyc_proved_with() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .is_kyc_proved_with(vec![first_provider_package_hash], true)
+        .expect_success();
+    proxy
+        .is_kyc_proved_with(vec![second_provider_package_hash], false)
+        .expect_success();
+    proxy.ban_provider(first_provider_package_hash);
+    proxy
+        .is_kyc_proved_with(
+            vec![first_provider_package_hash, second_provider_package_hash],
+            false,
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(314)"]
+fn test_is_kyc_proved_with_unknown_providers() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy
+        .is_kyc_proved_with(vec![first_provider_package_hash], false)
+        .expect_success();
+}
+
+#[test]
+fn test_usage_metering() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    let (second_provider_package_hash, _) = proxy.deploy_kyc(proxy.participant_two.1, "second");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc_provider(second_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    let (consumer_package_hash, consumer_hash) = proxy.deploy_consumer("consumer");
+    // nothing is counted until metering is turned on
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+    assert_eq!(proxy.get_contract_usage(consumer_package_hash), 0);
+
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::set_usage_metering(true))
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+    proxy
+        .check_through_consumer(consumer_hash, proxy.participant_three.1, false)
+        .expect_success();
+    assert_eq!(proxy.get_contract_usage(consumer_package_hash), 2);
+    assert_eq!(
+        proxy.get_provider_usage(first_provider_package_hash),
+        ProviderUsage {
+            calls: 2,
+            approvals: 1
+        }
+    );
+    assert_eq!(
+        proxy.get_provider_usage(second_provider_package_hash),
+        ProviderUsage {
+            calls: 1,
+            approvals: 0
+        }
+    );
+
+    proxy
+        .call(proxy.admin_account.1, ProxyCall::reset_usage())
+        .expect_success();
+    assert_eq!(proxy.get_contract_usage(consumer_package_hash), 0);
+    assert_eq!(
+        proxy.get_provider_usage(first_provider_package_hash),
+        ProviderUsage::default()
+    );
+    proxy
+        .check_through_consumer(consumer_hash, proxy.admin_account.1, true)
+        .expect_success();
+    assert_eq!(proxy.get_contract_usage(consumer_package_hash), 1);
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_set_usage_metering_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .call(proxy.participant_two.1, ProxyCall::set_usage_metering(true))
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(315)"]
+fn test_reset_usage_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    proxy
+        .call(proxy.participant_two.1, ProxyCall::reset_usage())
+        .expect_success();
+}
+
+#[test]
+fn test_provider_expiry() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy
+        .call_provider(
+            proxy.participant_two.1,
+            first_provider_hash.value(),
+            ProviderCall::approve(Key::Account(proxy.admin_account.1), None, Some(2_000)),
+        )
+        .expect_success();
+    proxy
+        .record_kyc_verdict(proxy.admin_account.1, None, 1_999)
+        .expect_success();
+    assert_eq!(proxy.get_kyc_verdict(proxy.admin_account.1), (true, 1_999));
+    proxy
+        .record_kyc_verdict(proxy.admin_account.1, None, 2_000)
+        .expect_success();
+    assert_eq!(proxy.get_kyc_verdict(proxy.admin_account.1), (false, 2_000));
+}
+
+#[test]
+fn test_provider_admins() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_three.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved(false).expect_success();
+    proxy
+        .call_provider(
+            proxy.participant_two.1,
+            first_provider_hash.value(),
+            ProviderCall::add_admin(Key::Account(proxy.participant_three.1)),
+        )
+        .expect_success();
+    proxy.add_kyc(
+        proxy.participant_three.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+#[should_panic = "User(400)"]
+fn test_provider_approve_by_non_admin() {
+    let mut proxy = ProxyContract::deploy();
+    let (_, first_provider_hash) = proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy
+        .call_provider(
+            proxy.participant_three.1,
+            first_provider_hash.value(),
+            ProviderCall::approve(Key::Account(proxy.admin_account.1), None, None),
+        )
+        .expect_success();
+}
+
+#[test]
+fn test_mock_provider_answers() {
+    let mut proxy = ProxyContract::deploy();
+    let (mock_package_hash, mock_hash) = proxy.deploy_mock_provider("mock");
+    proxy.add_kyc_provider(mock_package_hash);
+    proxy.is_kyc_proved(false).expect_success();
+    proxy.set_mock_answer(mock_hash, proxy.admin_account.1, MockAnswer::True);
+    proxy.is_kyc_proved(true).expect_success();
+    proxy.set_mock_answer(mock_hash, proxy.admin_account.1, MockAnswer::False);
+    proxy.is_kyc_proved(false).expect_success();
+}
+
+#[test]
+fn test_provider_addressed_by_contract_hash() {
+    let mut proxy = ProxyContract::deploy();
+    let (_, mock_hash) = proxy.deploy_mock_provider("mock");
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(ContractPackageHash::new(mock_hash.value()))
+                .with_addressing(ProviderAddressing::Contract),
+        )
+        .expect_success();
+    proxy.is_kyc_proved(false).expect_success();
+    proxy.set_mock_answer(mock_hash, proxy.admin_account.1, MockAnswer::True);
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+fn test_is_kyc_proved_for_public_key() {
+    let mut proxy = ProxyContract::deploy();
+    let (first_provider_package_hash, first_provider_hash) =
+        proxy.deploy_kyc(proxy.participant_two.1, "first");
+    proxy.add_kyc_provider(first_provider_package_hash);
+    proxy.add_kyc(
+        proxy.participant_two.1,
+        first_provider_hash.value(),
+        proxy.admin_account.1,
+    );
+    proxy
+        .is_kyc_proved_for_public_key(proxy.admin_account.0.clone(), true)
+        .expect_success();
+    proxy
+        .is_kyc_proved_for_public_key(proxy.participant_two.0.clone(), false)
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(306)"]
+fn test_account_normalization_rejects_hash_key() {
+    let mut proxy = ProxyContract::deploy();
+    let (mock_package_hash, _) = proxy.deploy_mock_provider("mock");
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(mock_package_hash)
+                .with_normalization(AccountNormalization::AccountOnly),
+        )
+        .expect_success();
+    proxy
+        .check_through_session(runtime_args! {
+            "entry_point" => entry_points::ANY_KYC_PROVED,
+            args::ACCOUNTS => vec![Key::Hash([1; 32])],
+            "result" => false
+        })
+        .expect_success();
+}
+
+#[test]
+fn test_account_normalization_skips_provider() {
+    let mut proxy = ProxyContract::deploy();
+    let (account_package_hash, _) = proxy.deploy_mock_provider("account");
+    let (verbatim_package_hash, verbatim_hash) = proxy.deploy_mock_provider("verbatim");
+    proxy
+        .add_kyc_provider_with(
+            AddKycProvider::new(account_package_hash)
+                .with_normalization(AccountNormalization::AccountOnly),
+        )
+        .expect_success();
+    proxy.add_kyc_provider(verbatim_package_hash);
+    proxy.set_mock_default_answer(verbatim_hash, MockAnswer::True);
+    proxy
+        .check_through_session(runtime_args! {
+            "entry_point" => entry_points::ANY_KYC_PROVED,
+            args::ACCOUNTS => vec![Key::Hash([1; 32])],
+            "result" => true
+        })
+        .expect_success();
+}
+
+#[test]
+fn test_mock_provider_default_answer() {
+    let mut proxy = ProxyContract::deploy();
+    let (mock_package_hash, mock_hash) = proxy.deploy_mock_provider("mock");
+    proxy.add_kyc_provider(mock_package_hash);
+    proxy.set_mock_default_answer(mock_hash, MockAnswer::True);
+    proxy.set_mock_answer(mock_hash, proxy.participant_two.1, MockAnswer::False);
+    proxy
+        .are_kyc_proved(
+            vec![proxy.admin_account.1, proxy.participant_two.1],
+            vec![true, false],
+        )
+        .expect_success();
+}
+
+#[test]
+#[should_panic = "User(500)"]
+fn test_mock_provider_revert() {
+    let mut proxy = ProxyContract::deploy();
+    let (mock_package_hash, mock_hash) = proxy.deploy_mock_provider("mock");
+    proxy.add_kyc_provider(mock_package_hash);
+    proxy.set_mock_answer(mock_hash, proxy.admin_account.1, MockAnswer::Revert);
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+#[test]
+#[should_panic = "GasLimit"]
+fn test_mock_provider_burn_gas() {
+    let mut proxy = ProxyContract::deploy();
+    let (mock_package_hash, mock_hash) = proxy.deploy_mock_provider("mock");
+    proxy.add_kyc_provider(mock_package_hash);
+    proxy.set_mock_answer(mock_hash, proxy.admin_account.1, MockAnswer::BurnGas);
+    proxy.is_kyc_proved(true).expect_success();
+}
+
+/// Gas of a check asking `provider_count` mock providers that all refuse, so each one is called,
+/// through the proxy `install` deploys.
+fn check_gas(install: fn() -> ProxyContract, provider_count: u64) -> U512 {
+    let mut proxy = install();
+    for provider in 0..provider_count {
+        let (mock_package_hash, _) = proxy.deploy_mock_provider(&format!("mock-{}", provider));
+        proxy.add_kyc_provider(mock_package_hash);
+    }
+    proxy
+        .is_kyc_proved(false)
+        .expect_success()
+        .last_exec_gas_cost()
+        .value()
+}
+
+/// Gas benchmark of a check at 1, 10 and 50 providers, printed with `--ignored --nocapture`.
+/// It compares the first release, which reads an index and a status from the "kyc_providers"
+/// dictionary for every provider, with the current proxy reading the "kyc_active_providers" list.
+#[test]
+#[ignore]
+fn bench_check_gas() {
+    let layouts: [(&str, fn() -> ProxyContract); 2] = [
+        ("kyc_providers", ProxyContract::deploy_v1),
+        ("kyc_active_providers", ProxyContract::deploy),
+    ];
+    println!(
+        "{:>9} {:>24} {:>24}",
+        "providers", layouts[0].0, layouts[1].0
+    );
+    let mut rows: Vec<(u64, [U512; 2])> = Vec::new();
+    for provider_count in [1u64, 10, 50] {
+        let gas = [
+            check_gas(layouts[0].1, provider_count),
+            check_gas(layouts[1].1, provider_count),
+        ];
+        println!("{:>9} {:>24} {:>24}", provider_count, gas[0], gas[1]);
+        rows.push((provider_count, gas));
+    }
+    let (first_count, first_gas) = rows[0];
+    let (last_count, last_gas) = rows[rows.len() - 1];
+    let added = U512::from(last_count - first_count);
+    println!(
+        "{:>9} {:>24} {:>24}",
+        "per added",
+        (last_gas[0] - first_gas[0]) / added,
+        (last_gas[1] - first_gas[1]) / added
+    );
+}
+
+/// Number of mock providers the registry operations of `test_registry_operations` pick from.
+const MODEL_PROVIDERS: usize = 3;
+
+/// A registry operation on one of the mock providers.
+#[derive(Clone, Copy, Debug)]
+enum RegistryOp {
+    Add(usize),
+    Ban(usize),
+    Unban(usize),
+}
+
+fn registry_op() -> impl Strategy<Value = RegistryOp> {
+    prop_oneof![
+        (0..MODEL_PROVIDERS).prop_map(RegistryOp::Add),
+        (0..MODEL_PROVIDERS).prop_map(RegistryOp::Ban),
+        (0..MODEL_PROVIDERS).prop_map(RegistryOp::Unban),
+    ]
+}
+
+/// Reference model of the registry: providers in registration order with their active status.
+#[derive(Default)]
+struct RegistryModel {
+    providers: Vec<(usize, bool)>,
+}
+
+impl RegistryModel {
+    fn apply(&mut self, op: RegistryOp) {
+        match op {
+            RegistryOp::Add(provider) => {
+                if !self.providers.iter().any(|(stored, _)| *stored == provider) {
+                    self.providers.push((provider, true));
+                }
+            }
+            RegistryOp::Ban(provider) | RegistryOp::Unban(provider) => {
+                let active = matches!(op, RegistryOp::Unban(_));
+                if let Some(entry) = self
+                    .providers
+                    .iter_mut()
+                    .find(|(stored, _)| *stored == provider)
+                {
+                    entry.1 = active;
+                }
+            }
+        }
+    }
+
+    /// Whether an active provider approves, given which providers approve the account.
+    fn is_kyc_proved(&self, approvals: &[bool]) -> bool {
+        self.providers
+            .iter()
+            .any(|(provider, active)| *active && approvals[*provider])
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn test_registry_operations(
+        approvals in proptest::collection::vec(any::<bool>(), MODEL_PROVIDERS),
+        ops in proptest::collection::vec(registry_op(), 1..10),
+    ) {
+        let mut proxy = ProxyContract::deploy();
+        let mut mocks = Vec::new();
+        for (provider, approves) in approvals.iter().enumerate() {
+            let (mock_package_hash, mock_hash) =
+                proxy.deploy_mock_provider(&format!("mock_{}", provider));
+            if *approves {
+                proxy.set_mock_answer(mock_hash, proxy.admin_account.1, MockAnswer::True);
+            }
+            mocks.push(mock_package_hash);
+        }
+        let mut model = RegistryModel::default();
+        for op in ops {
+            match op {
+                RegistryOp::Add(provider) => proxy.add_kyc_provider(mocks[provider]),
+                RegistryOp::Ban(provider) => proxy.ban_provider(mocks[provider]),
+                RegistryOp::Unban(provider) => proxy.unban_provider(mocks[provider]),
+            }
+            model.apply(op);
+
+            let snapshot = proxy.registry_snapshot();
+            let expected: Vec<RegistryEntry> = model
+                .providers
+                .iter()
+                .enumerate()
+                .map(|(index, (provider, active))| RegistryEntry {
+                    index: index as u64,
+                    package_hash: mocks[*provider],
+                    status: if *active {
+                        ProviderStatus::Active
+                    } else {
+                        ProviderStatus::Banned {
+                            reason: String::new(),
+                        }
+                    },
+                })
+                .collect();
+            prop_assert_eq!(&snapshot.providers, &expected);
+            proxy
+                .is_kyc_proved(model.is_kyc_proved(&approvals))
+                .expect_success();
+        }
+    }
+}